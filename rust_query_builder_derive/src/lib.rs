@@ -0,0 +1,310 @@
+//! Derive macros for `rust_query_builder`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, Ident, LitStr};
+
+/// Derives `Table` and `ToColumns` for a struct with named fields, so the
+/// struct-driven methods of `QueryBuilder` read its columns without any
+/// runtime type probing.
+///
+/// Container attributes, `#[table(...)]`:
+/// - `name = "..."`: the table name, the struct name in snake_case by default.
+/// - `rename_all = "..."`: how field names become column names, one of
+///   `snake_case`, `camelCase`, `PascalCase` or `SCREAMING_SNAKE_CASE`.
+///
+/// Field attributes, `#[column(...)]`:
+/// - `name = "..."`: the column name, overriding `rename_all`.
+/// - `skip`: not a column.
+/// - `primary_key`: identifies the row, so `UPDATE` never sets it.
+/// - `skip_insert`: generated by the database, so `INSERT` never writes it.
+/// - `default`: has a database default, which `INSERT` keeps when the value is `None`.
+#[proc_macro_derive(Table, attributes(table, column))]
+pub fn derive_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_table(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives `FromRow` for a struct with named fields, reading each column
+/// through `FromSqlValue`. It honours the same `#[table(rename_all = "...")]`
+/// and `#[column(name = "...")]` attributes as `Table`, so a struct
+/// round-trips through `INSERT` and `SELECT`; `#[column(skip)]` fields are
+/// filled with `Default::default()`.
+#[proc_macro_derive(FromRow, attributes(table, column))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_from_row(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct Column {
+    ident: Ident,
+    name: String,
+    skip: bool,
+    primary_key: bool,
+    skip_insert: bool,
+    default: bool,
+}
+
+/// Reads the container attributes and the columns of a struct with named
+/// fields, returning the table name and every field, skipped or not.
+fn parse_struct(input: &DeriveInput, derive: &str) -> syn::Result<(String, Vec<Column>)> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    format!(
+                        "{} can only be derived for structs with named fields",
+                        derive
+                    ),
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} can only be derived for structs", derive),
+            ))
+        }
+    };
+
+    let mut table_name = rename(&input.ident.unraw().to_string(), "snake_case");
+    let mut rename_all = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("table") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                table_name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("rename_all") {
+                let rule = meta.value()?.parse::<LitStr>()?;
+                if !RENAME_RULES.contains(&rule.value().as_str()) {
+                    return Err(syn::Error::new_spanned(
+                        rule,
+                        format!("rename_all must be one of {}", RENAME_RULES.join(", ")),
+                    ));
+                }
+                rename_all = Some(rule.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute"))
+            }
+        })?;
+    }
+
+    let columns = fields
+        .iter()
+        .map(|field| parse_column(field, rename_all.as_deref()))
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok((table_name, columns))
+}
+
+fn expand_table(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (table_name, mut columns) = parse_struct(input, "Table")?;
+    columns.retain(|column| !column.skip);
+
+    let names = columns
+        .iter()
+        .map(|column| &column.name)
+        .collect::<Vec<_>>();
+    let primary_key = columns
+        .iter()
+        .filter(|column| column.primary_key)
+        .map(|column| &column.name);
+    let all = columns.iter().map(|column| push_column(column, false));
+    let insert = columns
+        .iter()
+        .filter(|column| !column.skip_insert)
+        .map(|column| push_column(column, column.default));
+    let update = columns
+        .iter()
+        .filter(|column| !column.primary_key)
+        .map(|column| push_column(column, false));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rust_query_builder::Table for #ident #ty_generics #where_clause {
+            const TABLE_NAME: &'static str = #table_name;
+            const COLUMNS: &'static [&'static str] = &[#(#names),*];
+            const PRIMARY_KEY: &'static [&'static str] = &[#(#primary_key),*];
+        }
+
+        impl #impl_generics ::rust_query_builder::ToColumns for #ident #ty_generics #where_clause {
+            fn to_columns(&self) -> ::std::io::Result<::std::vec::Vec<(::std::string::String, ::rust_query_builder::Value)>> {
+                let mut columns = ::std::vec::Vec::new();
+                #(#all)*
+                ::std::result::Result::Ok(columns)
+            }
+
+            fn insert_columns(&self) -> ::std::io::Result<::std::vec::Vec<(::std::string::String, ::rust_query_builder::Value)>> {
+                let mut columns = ::std::vec::Vec::new();
+                #(#insert)*
+                ::std::result::Result::Ok(columns)
+            }
+
+            fn update_columns(&self) -> ::std::io::Result<::std::vec::Vec<(::std::string::String, ::rust_query_builder::Value)>> {
+                let mut columns = ::std::vec::Vec::new();
+                #(#update)*
+                ::std::result::Result::Ok(columns)
+            }
+        }
+    })
+}
+
+/// Reads the `#[column(...)]` attributes of a field.
+fn parse_column(field: &Field, rename_all: Option<&str>) -> syn::Result<Column> {
+    let ident = field.ident.clone().expect("named field");
+    let mut column = Column {
+        name: rename(
+            &ident.unraw().to_string(),
+            rename_all.unwrap_or("snake_case"),
+        ),
+        ident,
+        skip: false,
+        primary_key: false,
+        skip_insert: false,
+        default: false,
+    };
+    for attr in &field.attrs {
+        if !attr.path().is_ident("column") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                column.name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("skip") {
+                column.skip = true;
+            } else if meta.path.is_ident("primary_key") {
+                column.primary_key = true;
+            } else if meta.path.is_ident("skip_insert") {
+                column.skip_insert = true;
+            } else if meta.path.is_ident("default") {
+                column.default = true;
+            } else {
+                return Err(meta.error("unsupported column attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(column)
+}
+
+fn expand_from_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (_, columns) = parse_struct(input, "FromRow")?;
+    let fields = columns.iter().map(
+        |Column {
+             ident, name, skip, ..
+         }| {
+            if *skip {
+                quote! { #ident: ::core::default::Default::default() }
+            } else {
+                quote! { #ident: row.get_as(#name)? }
+            }
+        },
+    );
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rust_query_builder::FromRow for #ident #ty_generics #where_clause {
+            fn from_row(row: &::rust_query_builder::Row) -> ::std::io::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    #(#fields),*
+                })
+            }
+        }
+    })
+}
+
+/// Pushes the column/value pair of a field, leaving it out when it is
+/// `NULL` and `omit_null` is set.
+fn push_column(column: &Column, omit_null: bool) -> TokenStream2 {
+    let Column { ident, name, .. } = column;
+    let value = quote! {
+        ::rust_query_builder::ToSqlValue::to_sql_value(&self.#ident)
+    };
+    if omit_null {
+        quote! {
+            let value = #value;
+            if !value.is_null() {
+                columns.push((::std::string::String::from(#name), value));
+            }
+        }
+    } else {
+        quote! {
+            columns.push((::std::string::String::from(#name), #value));
+        }
+    }
+}
+
+const RENAME_RULES: [&str; 4] = [
+    "snake_case",
+    "camelCase",
+    "PascalCase",
+    "SCREAMING_SNAKE_CASE",
+];
+
+/// Renames a Rust identifier, either a snake_case field or a PascalCase
+/// struct name, according to a `rename_all` rule.
+fn rename(name: &str, rule: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars = part.chars().collect::<Vec<char>>();
+        let mut word = String::new();
+        for (index, &c) in chars.iter().enumerate() {
+            // A capital starts a word, except inside a run of capitals such
+            // as `API`, whose last letter starts the next word in `APIKey`.
+            let previous = index.checked_sub(1).map(|index| chars[index]);
+            let next = chars.get(index + 1);
+            let boundary = c.is_uppercase()
+                && match previous {
+                    Some(previous) if previous.is_uppercase() => {
+                        next.is_some_and(|next| next.is_lowercase())
+                    }
+                    Some(_) => true,
+                    None => false,
+                };
+            if boundary && !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            word.extend(c.to_lowercase());
+        }
+        words.push(word);
+    }
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    };
+    match rule {
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                if index == 0 {
+                    word.clone()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        _ => words.join("_"),
+    }
+}
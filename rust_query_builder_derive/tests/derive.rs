@@ -0,0 +1,51 @@
+use rust_query_builder::{FromRow, Row, Table, ToColumns, Value};
+
+#[test]
+fn should_name_raw_identifier_fields_without_the_prefix() {
+    #[derive(Table, FromRow, Debug, PartialEq)]
+    struct Token {
+        r#type: String,
+        #[column(name = "ref")]
+        r#ref: i32,
+    }
+    let token = Token {
+        r#type: String::from("bearer"),
+        r#ref: 1,
+    };
+
+    assert_eq!(Token::COLUMNS, ["type", "ref"]);
+    assert_eq!(
+        token.to_columns().unwrap(),
+        vec![
+            (String::from("type"), Value::from("bearer")),
+            (String::from("ref"), Value::from(1)),
+        ]
+    );
+    let row = Row::from_iter([("type", Value::from("bearer")), ("ref", Value::from(1))]);
+    assert_eq!(Token::from_row(&row).unwrap(), token);
+}
+
+#[test]
+fn should_keep_runs_of_capitals_as_one_word() {
+    #[derive(Table)]
+    #[allow(dead_code)]
+    struct APIKey {
+        key_id: i32,
+    }
+    #[derive(Table)]
+    #[table(rename_all = "camelCase")]
+    #[allow(dead_code, non_snake_case)]
+    struct HTTPRequest {
+        userID: i32,
+        request_URL: String,
+    }
+
+    assert_eq!(APIKey::TABLE_NAME, "api_key");
+    assert_eq!(HTTPRequest::TABLE_NAME, "http_request");
+    assert_eq!(HTTPRequest::COLUMNS, ["userId", "requestUrl"]);
+}
+
+#[test]
+fn should_report_invalid_attributes_at_their_span() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+use rust_query_builder::{FromRow, Table};
+
+#[derive(Table)]
+struct Pair(i32, i32);
+
+#[derive(FromRow)]
+enum Status {
+    Active,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rust_query_builder::Table;
+
+#[derive(Table)]
+struct User {
+    #[column(unique)]
+    email: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rust_query_builder::Table;
+
+#[derive(Table)]
+#[table(schema = "public")]
+struct User {
+    email: String,
+}
+
+fn main() {}
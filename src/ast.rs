@@ -0,0 +1,1210 @@
+use std::io;
+
+use crate::{dialect::UpsertSyntax, sql_injection_prevention, Dialect, Value};
+
+/// The kind of statement being built, decided by the main clause
+/// (`SELECT`, `INSERT`, `UPDATE` or `DELETE`) regardless of the order of the calls.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StatementKind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+/// An expression usable in the select list and in predicates.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    Column(String),
+    Value(Value),
+    Aggregate {
+        function: &'static str,
+        distinct: bool,
+        argument: Box<Expr>,
+    },
+    Alias(Box<Expr>, String),
+    Subquery(Subquery),
+    /// SQL inserted verbatim, never escaped.
+    Raw(String),
+    /// The value a conflicting INSERT proposed for a column.
+    Excluded(String),
+    /// The column's default in a row of a multi-row INSERT.
+    Default,
+}
+
+/// Another query embedded in a FROM, JOIN, predicate or select list.
+#[derive(Clone, Debug)]
+pub struct Subquery(pub(crate) Box<Statement>);
+
+#[allow(non_snake_case)]
+impl Expr {
+    /// Applies DISTINCT to the argument of an aggregate expression.
+    pub fn DISTINCT(self) -> Self {
+        match self {
+            Expr::Aggregate {
+                function, argument, ..
+            } => Expr::Aggregate {
+                function,
+                distinct: true,
+                argument,
+            },
+            Expr::Alias(expr, alias) => Expr::Alias(Box::new(expr.DISTINCT()), alias),
+            expr => expr,
+        }
+    }
+
+    /// Names the expression in the select list.
+    pub fn AS(self, alias: &str) -> Self {
+        Expr::Alias(Box::new(self), alias.to_string())
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(column: &str) -> Self {
+        Expr::Column(column.to_string())
+    }
+}
+
+impl From<&String> for Expr {
+    fn from(column: &String) -> Self {
+        Expr::Column(column.clone())
+    }
+}
+
+impl From<String> for Expr {
+    fn from(column: String) -> Self {
+        Expr::Column(column)
+    }
+}
+
+/// The right-hand side of a comparison. Plain values are bound as values;
+/// pass an `Expr` (see `col` and `raw`) to compare against a column or an
+/// expression instead.
+pub trait Operand {
+    fn into_expr(self) -> Expr;
+}
+
+impl<T> Operand for T
+where
+    T: Into<Value>,
+{
+    fn into_expr(self) -> Expr {
+        Expr::Value(self.into())
+    }
+}
+
+impl Operand for Expr {
+    fn into_expr(self) -> Expr {
+        self
+    }
+}
+
+/// The condition list that `AND`, `OR`, `AND_NOT` and `OR_NOT` extend: the
+/// one opened by the last `WHERE`, `HAVING` or `DO_UPDATE_WHERE` call.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum ConditionTarget {
+    #[default]
+    Where,
+    Having,
+    ConflictUpdate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Connector {
+    And,
+    Or,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Predicate {
+    Compare {
+        left: Expr,
+        operator: String,
+        right: Expr,
+    },
+    In {
+        left: Expr,
+        negated: bool,
+        subquery: Subquery,
+    },
+    Exists(Subquery),
+    InList {
+        left: Expr,
+        negated: bool,
+        values: Vec<Expr>,
+    },
+    Between {
+        left: Expr,
+        negated: bool,
+        low: Expr,
+        high: Expr,
+    },
+    Like {
+        left: Expr,
+        negated: bool,
+        case_insensitive: bool,
+        pattern: Expr,
+        escape: Option<char>,
+    },
+    IsNull {
+        left: Expr,
+        negated: bool,
+    },
+    IsDistinctFrom {
+        left: Expr,
+        negated: bool,
+        right: Expr,
+    },
+    All(Vec<Predicate>),
+    Any(Vec<Predicate>),
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    /// Whether the predicate must be parenthesised next to other predicates.
+    fn is_compound(&self) -> bool {
+        match self {
+            Predicate::All(predicates) | Predicate::Any(predicates) => {
+                match predicates.as_slice() {
+                    [predicate] => predicate.is_compound(),
+                    predicates => predicates.len() > 1,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// One link of a `WHERE ... AND ... OR ...` chain. The connector of the
+/// first item is never rendered.
+#[derive(Clone, Debug)]
+pub(crate) struct ConditionItem {
+    pub connector: Connector,
+    pub negated: bool,
+    pub predicate: Predicate,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum TableSource {
+    Table(String),
+    Subquery(Subquery),
+}
+
+/// A table or subquery referenced in FROM or JOIN, optionally aliased.
+#[derive(Clone, Debug)]
+pub struct TableRef {
+    pub(crate) source: TableSource,
+    pub(crate) alias: Option<String>,
+}
+
+impl From<&str> for TableRef {
+    fn from(name: &str) -> Self {
+        TableRef {
+            source: TableSource::Table(name.to_string()),
+            alias: None,
+        }
+    }
+}
+
+impl From<(&str, &str)> for TableRef {
+    fn from((name, alias): (&str, &str)) -> Self {
+        TableRef {
+            source: TableSource::Table(name.to_string()),
+            alias: Some(alias.to_string()),
+        }
+    }
+}
+
+impl From<(Subquery, &str)> for TableRef {
+    fn from((subquery, alias): (Subquery, &str)) -> Self {
+        TableRef {
+            source: TableSource::Subquery(subquery),
+            alias: Some(alias.to_string()),
+        }
+    }
+}
+
+/// The name of a common table expression, with an optional column list:
+/// `"name"` or `("name", &["column", ...])`.
+#[derive(Clone, Debug)]
+pub struct CteName {
+    pub(crate) name: String,
+    pub(crate) columns: Vec<String>,
+}
+
+impl From<&str> for CteName {
+    fn from(name: &str) -> Self {
+        CteName {
+            name: name.to_string(),
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl From<(&str, &[&str])> for CteName {
+    fn from((name, columns): (&str, &[&str])) -> Self {
+        CteName {
+            name: name.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+        }
+    }
+}
+
+impl<const N: usize> From<(&str, &[&str; N])> for CteName {
+    fn from((name, columns): (&str, &[&str; N])) -> Self {
+        (name, &columns[..]).into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Cte {
+    pub name: CteName,
+    /// `Some(true)` for MATERIALIZED, `Some(false)` for NOT MATERIALIZED.
+    pub materialized: Option<bool>,
+    pub query: Subquery,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl SetOperator {
+    fn keyword(&self) -> &'static str {
+        match self {
+            SetOperator::Union => "UNION",
+            SetOperator::UnionAll => "UNION ALL",
+            SetOperator::Intersect => "INTERSECT",
+            SetOperator::Except => "EXCEPT",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct SetOperation {
+    pub operator: SetOperator,
+    pub query: Subquery,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+impl JoinKind {
+    fn keyword(&self) -> &'static str {
+        match self {
+            JoinKind::Inner => "JOIN",
+            JoinKind::Left => "LEFT JOIN",
+            JoinKind::Right => "RIGHT JOIN",
+            JoinKind::Full => "FULL JOIN",
+            JoinKind::Cross => "CROSS JOIN",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum JoinConstraint {
+    On(Vec<Predicate>),
+    Using(Vec<String>),
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Join {
+    pub kind: JoinKind,
+    pub table: TableRef,
+    pub constraint: JoinConstraint,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum ConflictTarget {
+    None,
+    Columns(Vec<String>),
+    Constraint(String),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum ConflictAction {
+    Nothing,
+    Update(Vec<Assignment>),
+}
+
+/// The upsert clause of an INSERT.
+#[derive(Clone, Debug)]
+pub(crate) struct OnConflict {
+    pub target: ConflictTarget,
+    pub action: Option<ConflictAction>,
+    /// The condition a DO UPDATE only updates the matching rows under.
+    pub selection: Vec<ConditionItem>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Assignment {
+    Set(String, Expr),
+    Raw(String),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct OrderBy {
+    pub expr: Expr,
+    pub direction: Option<String>,
+}
+
+/// Number of columns a select list produces, unknown when it has a wildcard.
+fn projection_arity(projection: &[Expr]) -> Option<usize> {
+    let wildcard = projection.iter().any(|expr| match expr {
+        Expr::Column(column) => column == "*" || column.ends_with(".*"),
+        _ => false,
+    });
+    if wildcard {
+        None
+    } else {
+        Some(projection.len())
+    }
+}
+
+/// The statement tree built by `SqlQueryBuilder`, serialized only on build.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Statement {
+    pub kind: Option<StatementKind>,
+    pub recursive: bool,
+    pub with: Vec<Cte>,
+    pub table: String,
+    pub distinct: bool,
+    pub projection: Vec<Expr>,
+    pub from: Vec<TableRef>,
+    pub joins: Vec<Join>,
+    pub selection: Vec<ConditionItem>,
+    pub group_by: Vec<Expr>,
+    pub having: Vec<ConditionItem>,
+    pub set_operations: Vec<SetOperation>,
+    pub order_by: Vec<OrderBy>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Expr>>,
+    /// The query an INSERT takes its rows from, instead of `rows`.
+    pub source: Option<Subquery>,
+    pub assignments: Vec<Assignment>,
+    /// The tables a DELETE joins with, besides `joins`.
+    pub using: Vec<TableRef>,
+    pub on_conflict: Option<OnConflict>,
+    pub returning: Vec<Expr>,
+    pub error_message: Option<String>,
+}
+
+/// Operators accepted between the two sides of a comparison predicate.
+const PREDICATE_OPERATORS: [&str; 13] = [
+    "=",
+    "<>",
+    "!=",
+    "<",
+    "<=",
+    ">",
+    ">=",
+    "LIKE",
+    "NOT LIKE",
+    "ILIKE",
+    "NOT ILIKE",
+    "IS",
+    "IS NOT",
+];
+
+/// Returns `true` if `operator` may be rendered between two operands.
+pub(crate) fn is_predicate_operator(operator: &str) -> bool {
+    PREDICATE_OPERATORS.contains(&operator.trim().to_uppercase().as_str())
+}
+
+/// Serializes a `Statement` for a dialect, inlining the values when no
+/// parameter list is given and binding them behind placeholders otherwise.
+pub(crate) struct Renderer<'a, D: Dialect> {
+    dialect: &'a D,
+    params: Option<&'a mut Vec<Value>>,
+    /// First error raised while rendering, e.g. in a nested query.
+    error: Option<String>,
+}
+
+impl<'a, D: Dialect> Renderer<'a, D> {
+    pub fn new(dialect: &'a D, params: Option<&'a mut Vec<Value>>) -> Self {
+        Self {
+            dialect,
+            params,
+            error: None,
+        }
+    }
+
+    fn identifier(&self, identifier: &str) -> String {
+        self.dialect.quote_identifier(identifier)
+    }
+
+    fn identifiers(&self, identifiers: &[String]) -> String {
+        identifiers
+            .iter()
+            .map(|identifier| self.identifier(identifier))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn table_ref(&mut self, table: &TableRef) -> String {
+        let source = match &table.source {
+            TableSource::Table(name) => self.identifier(name),
+            TableSource::Subquery(subquery) => self.subquery(subquery),
+        };
+        match &table.alias {
+            Some(alias) => format!("{} AS {}", source, self.identifier(alias)),
+            None => source,
+        }
+    }
+
+    fn table_refs(&mut self, tables: &[TableRef]) -> String {
+        tables
+            .iter()
+            .map(|table| self.table_ref(table))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn subquery(&mut self, subquery: &Subquery) -> String {
+        match self.statement(&subquery.0) {
+            Ok(query) => format!("({})", query),
+            Err(error) => {
+                self.error.get_or_insert(error.to_string());
+                String::new()
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Value) -> String {
+        match self.params.as_deref_mut() {
+            Some(params) => {
+                params.push(value.clone());
+                self.dialect.placeholder(params.len())
+            }
+            // SQL has no literal for NaN or infinity; bound values are left to the driver.
+            None => match value {
+                Value::Float(float) if !float.is_finite() => {
+                    self.error.get_or_insert(format!(
+                        "The non-finite float {} cannot be inlined, bind it with build_with_params",
+                        float
+                    ));
+                    String::new()
+                }
+                _ => self.dialect.render_value(value),
+            },
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Column(column) => self.identifier(column),
+            Expr::Value(value) => self.value(value),
+            Expr::Aggregate {
+                function,
+                distinct,
+                argument,
+            } => {
+                let distinct = if *distinct { "DISTINCT " } else { "" };
+                format!("{}({}{})", function, distinct, self.expr(argument))
+            }
+            Expr::Alias(expr, alias) => {
+                format!("{} AS {}", self.expr(expr), self.identifier(alias))
+            }
+            Expr::Subquery(subquery) => self.subquery(subquery),
+            Expr::Raw(sql) => sql.clone(),
+            Expr::Default if self.dialect.supports_default_values() => "DEFAULT".to_string(),
+            Expr::Default => "NULL".to_string(),
+            Expr::Excluded(column) => {
+                let column = self.identifier(column);
+                self.dialect.excluded(&column)
+            }
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) -> String {
+        exprs
+            .iter()
+            .map(|expr| self.expr(expr))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn predicate(&mut self, predicate: &Predicate) -> String {
+        match predicate {
+            Predicate::Compare {
+                left,
+                operator,
+                right,
+            } => {
+                if !is_predicate_operator(operator) {
+                    self.error
+                        .get_or_insert(format!("Invalid operator: {}", operator));
+                }
+                let left = self.expr(left);
+                let operator = sql_injection_prevention(operator);
+                let right = self.expr(right);
+                format!("{} {} {}", left, operator, right)
+            }
+            Predicate::In {
+                left,
+                negated,
+                subquery,
+            } => {
+                let left = self.expr(left);
+                let operator = if *negated { "NOT IN" } else { "IN" };
+                format!("{} {} {}", left, operator, self.subquery(subquery))
+            }
+            Predicate::Exists(subquery) => format!("EXISTS {}", self.subquery(subquery)),
+            Predicate::InList {
+                left,
+                negated,
+                values,
+            } => {
+                if values.is_empty() {
+                    // `IN ()` is not valid SQL: nothing is in an empty list.
+                    return if *negated { "1 = 1" } else { "1 = 0" }.to_string();
+                }
+                let left = self.expr(left);
+                let operator = if *negated { "NOT IN" } else { "IN" };
+                format!("{} {} ({})", left, operator, self.exprs(values))
+            }
+            Predicate::Between {
+                left,
+                negated,
+                low,
+                high,
+            } => {
+                let left = self.expr(left);
+                let operator = if *negated { "NOT BETWEEN" } else { "BETWEEN" };
+                let low = self.expr(low);
+                let high = self.expr(high);
+                format!("{} {} {} AND {}", left, operator, low, high)
+            }
+            Predicate::Like {
+                left,
+                negated,
+                case_insensitive,
+                pattern,
+                escape,
+            } => {
+                let mut left = self.expr(left);
+                let mut pattern = self.expr(pattern);
+                let operator = match (*case_insensitive, self.dialect.supports_ilike()) {
+                    (true, true) => "ILIKE",
+                    (true, false) => {
+                        left = format!("LOWER({})", left);
+                        pattern = format!("LOWER({})", pattern);
+                        "LIKE"
+                    }
+                    (false, _) => "LIKE",
+                };
+                let negation = if *negated { "NOT " } else { "" };
+                let mut like = format!("{} {}{} {}", left, negation, operator, pattern);
+                if let Some(escape) = escape {
+                    let escape = self.dialect.quote_string(&escape.to_string());
+                    like.push_str(&format!(" ESCAPE {}", escape));
+                }
+                like
+            }
+            Predicate::IsNull { left, negated } => {
+                let operator = if *negated { "IS NOT NULL" } else { "IS NULL" };
+                format!("{} {}", self.expr(left), operator)
+            }
+            Predicate::IsDistinctFrom {
+                left,
+                negated,
+                right,
+            } => {
+                let left = self.expr(left);
+                let right = self.expr(right);
+                self.dialect.is_distinct_from(&left, &right, *negated)
+            }
+            Predicate::All(predicates) => self.group(predicates, " AND ", "1 = 1"),
+            Predicate::Any(predicates) => self.group(predicates, " OR ", "1 = 0"),
+            Predicate::Not(predicate) => format!("NOT {}", self.nested_predicate(predicate)),
+        }
+    }
+
+    /// Renders a predicate that sits next to other predicates.
+    fn nested_predicate(&mut self, predicate: &Predicate) -> String {
+        if predicate.is_compound() {
+            format!("({})", self.predicate(predicate))
+        } else {
+            self.predicate(predicate)
+        }
+    }
+
+    fn group(&mut self, predicates: &[Predicate], separator: &str, empty: &str) -> String {
+        match predicates {
+            [] => empty.to_string(),
+            [predicate] => self.predicate(predicate),
+            predicates => predicates
+                .iter()
+                .map(|predicate| self.nested_predicate(predicate))
+                .collect::<Vec<String>>()
+                .join(separator),
+        }
+    }
+
+    fn condition(&mut self, items: &[ConditionItem]) -> String {
+        let mut condition = String::new();
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                condition.push_str(match item.connector {
+                    Connector::And => " AND ",
+                    Connector::Or => " OR ",
+                });
+            }
+            if item.negated {
+                condition.push_str("NOT ");
+            }
+            if items.len() == 1 && !item.negated {
+                condition.push_str(&self.predicate(&item.predicate));
+            } else {
+                condition.push_str(&self.nested_predicate(&item.predicate));
+            }
+        }
+        condition
+    }
+
+    pub fn statement(&mut self, statement: &Statement) -> io::Result<String> {
+        if let Some(error_message) = &statement.error_message {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                error_message.clone(),
+            ));
+        }
+        let mut clauses = Vec::new();
+        self.with_clause(statement, &mut clauses)?;
+        match statement.kind {
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "No query has been built",
+                ))
+            }
+            Some(StatementKind::Select) => {
+                if statement.set_operations.is_empty() {
+                    self.select_clauses(statement, &mut clauses)?;
+                } else {
+                    self.set_operation_clauses(statement, &mut clauses)?;
+                }
+                self.order_by_clause(statement, &mut clauses);
+                let pagination = self.dialect.limit_offset(statement.limit, statement.offset);
+                if !pagination.is_empty() {
+                    clauses.push(pagination);
+                }
+            }
+            Some(StatementKind::Insert) => {
+                let syntax = self.dialect.upsert_syntax();
+                let ignore = match &statement.on_conflict {
+                    Some(on_conflict) => {
+                        syntax == UpsertSyntax::OnDuplicateKeyUpdate
+                            && matches!(on_conflict.action, Some(ConflictAction::Nothing))
+                    }
+                    None => false,
+                };
+                if !statement.selection.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "An INSERT has no WHERE clause, use DO_UPDATE_WHERE to make its update conditional",
+                    ));
+                }
+                if statement.table.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "No table has been provided to INSERT INTO",
+                    ));
+                }
+                if statement.source.is_none() && statement.columns.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "No columns have been provided to INSERT",
+                    ));
+                }
+                let insert = if ignore { "INSERT IGNORE" } else { "INSERT" };
+                let mut into = format!("{} INTO {}", insert, self.identifier(&statement.table));
+                if !statement.columns.is_empty() {
+                    into.push_str(&format!(" ({})", self.identifiers(&statement.columns)));
+                }
+                clauses.push(into);
+                match &statement.source {
+                    Some(source) => self.insert_source(statement, source, &mut clauses)?,
+                    None if statement.rows.is_empty() => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "No rows have been provided to INSERT",
+                        ))
+                    }
+                    None => {
+                        let rows = statement
+                            .rows
+                            .iter()
+                            .map(|row| format!("({})", self.exprs(row)))
+                            .collect::<Vec<String>>()
+                            .join(", ");
+                        clauses.push(format!("VALUES {}", rows));
+                    }
+                }
+                if let Some(on_conflict) = &statement.on_conflict {
+                    self.upsert_clause(on_conflict, &mut clauses)?;
+                }
+                self.returning_clause(statement, &mut clauses)?;
+            }
+            Some(StatementKind::Update) => {
+                if !statement.using.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "USING only applies to a DELETE, use FROM to add tables to an UPDATE",
+                    ));
+                }
+                if statement.table.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "No table has been provided to UPDATE",
+                    ));
+                }
+                let mut tables = vec![self.identifier(&statement.table)];
+                if self.dialect.tables_before_set() {
+                    tables.push(self.table_refs(&statement.from));
+                    tables.retain(|table| !table.is_empty());
+                    clauses.push(format!("UPDATE {}", tables.join(", ")));
+                    self.join_clauses(statement, &mut clauses)?;
+                    let assignments = self.assignments(&statement.assignments);
+                    clauses.push(format!("SET {}", assignments));
+                } else {
+                    let assignments = self.assignments(&statement.assignments);
+                    clauses.push(format!("UPDATE {} SET {}", tables[0], assignments));
+                    self.other_tables("UPDATE", "FROM", &statement.from, statement, &mut clauses)?;
+                }
+                self.where_clause(statement, &mut clauses);
+                self.returning_clause(statement, &mut clauses)?;
+            }
+            Some(StatementKind::Delete) => {
+                let (table, target) = match (statement.table.as_str(), statement.from.first()) {
+                    ("", Some(table)) => {
+                        let rendered = self.table_ref(table);
+                        // A multi-table DELETE names its target by alias when it has one.
+                        match &table.alias {
+                            Some(alias) => (rendered, self.identifier(alias)),
+                            None => (rendered.clone(), rendered),
+                        }
+                    }
+                    ("", None) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "No table has been provided to DELETE FROM",
+                        ))
+                    }
+                    (_, Some(_)) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "DELETE has both a table and a FROM target, use only one",
+                        ))
+                    }
+                    (table, None) => (self.identifier(table), self.identifier(table)),
+                };
+                if self.dialect.tables_before_set() {
+                    if statement.using.is_empty() && statement.joins.is_empty() {
+                        clauses.push(format!("DELETE FROM {}", table));
+                    } else {
+                        let mut tables = vec![table.clone(), self.table_refs(&statement.using)];
+                        tables.retain(|table| !table.is_empty());
+                        clauses.push(format!("DELETE {} FROM {}", target, tables.join(", ")));
+                        self.join_clauses(statement, &mut clauses)?;
+                    }
+                } else {
+                    clauses.push(format!("DELETE FROM {}", table));
+                    match self.dialect.delete_using_keyword() {
+                        Some(keyword) => self.other_tables(
+                            "DELETE",
+                            keyword,
+                            &statement.using,
+                            statement,
+                            &mut clauses,
+                        )?,
+                        None if statement.using.is_empty() && statement.joins.is_empty() => {}
+                        None => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!(
+                                    "DELETE with other tables is not supported by the {} dialect",
+                                    self.dialect.name()
+                                ),
+                            ))
+                        }
+                    }
+                }
+                self.where_clause(statement, &mut clauses);
+                self.returning_clause(statement, &mut clauses)?;
+            }
+        }
+        if let Some(error) = self.error.take() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+        }
+        Ok(clauses.join(" "))
+    }
+
+    fn select_clauses(
+        &mut self,
+        statement: &Statement,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        let select = if statement.distinct {
+            "SELECT DISTINCT"
+        } else {
+            "SELECT"
+        };
+        clauses.push(format!("{} {}", select, self.exprs(&statement.projection)));
+        if !statement.from.is_empty() {
+            clauses.push(format!("FROM {}", self.table_refs(&statement.from)));
+        } else if !statement.table.is_empty() {
+            clauses.push(format!("FROM {}", self.identifier(&statement.table)));
+        }
+        self.join_clauses(statement, clauses)?;
+        self.where_clause(statement, clauses);
+        if !statement.group_by.is_empty() {
+            clauses.push(format!("GROUP BY {}", self.exprs(&statement.group_by)));
+        }
+        if !statement.having.is_empty() {
+            clauses.push(format!("HAVING {}", self.condition(&statement.having)));
+        }
+        Ok(())
+    }
+
+    /// Renders the select clauses of the statement combined with its set
+    /// operations, left to right; ORDER BY and pagination apply to the result.
+    fn set_operation_clauses(
+        &mut self,
+        statement: &Statement,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        let mut first = Vec::new();
+        self.select_clauses(statement, &mut first)?;
+        let mut combined = first.join(" ");
+        let arity = projection_arity(&statement.projection);
+        let mut previous: Option<SetOperator> = None;
+        for operation in &statement.set_operations {
+            let operand = &operation.query.0;
+            if operand.kind != Some(StatementKind::Select) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Only SELECT queries can be combined with {}",
+                        operation.operator.keyword()
+                    ),
+                ));
+            }
+            if let (Some(left), Some(right)) = (arity, projection_arity(&operand.projection)) {
+                if left != right {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "{} operands have different numbers of columns: {} and {}",
+                            operation.operator.keyword(),
+                            left,
+                            right
+                        ),
+                    ));
+                }
+            }
+            // INTERSECT binds tighter than UNION and EXCEPT, so keep the
+            // left-to-right order of the calls explicit.
+            if operation.operator == SetOperator::Intersect
+                && previous.is_some_and(|previous| previous != SetOperator::Intersect)
+                && self.dialect.supports_parenthesized_set_operands()
+            {
+                combined = format!("({})", combined);
+            }
+            let rendered = self.set_operand(operand)?;
+            combined = format!("{} {} {}", combined, operation.operator.keyword(), rendered);
+            previous = Some(operation.operator);
+        }
+        clauses.push(combined);
+        Ok(())
+    }
+
+    /// Renders the right-hand side of a set operation, parenthesised when it
+    /// carries clauses of its own that would otherwise apply to the result.
+    fn set_operand(&mut self, operand: &Statement) -> io::Result<String> {
+        let query = self.statement(operand)?;
+        let needs_parentheses = !operand.with.is_empty()
+            || !operand.set_operations.is_empty()
+            || !operand.order_by.is_empty()
+            || operand.limit.is_some()
+            || operand.offset.is_some();
+        if !needs_parentheses {
+            Ok(query)
+        } else if self.dialect.supports_parenthesized_set_operands() {
+            Ok(format!("({})", query))
+        } else {
+            Ok(format!("SELECT * FROM ({})", query))
+        }
+    }
+
+    /// Renders the tables an UPDATE or DELETE reads besides its target, as
+    /// `keyword tables joins`.
+    fn other_tables(
+        &mut self,
+        statement_name: &str,
+        keyword: &str,
+        tables: &[TableRef],
+        statement: &Statement,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        if tables.is_empty() {
+            if statement.joins.is_empty() {
+                return Ok(());
+            }
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} needs a {} table before its joins in the {} dialect",
+                    statement_name,
+                    keyword,
+                    self.dialect.name()
+                ),
+            ));
+        }
+        clauses.push(format!("{} {}", keyword, self.table_refs(tables)));
+        self.join_clauses(statement, clauses)
+    }
+
+    fn assignments(&mut self, assignments: &[Assignment]) -> String {
+        assignments
+            .iter()
+            .map(|assignment| match assignment {
+                Assignment::Set(column, value) => {
+                    format!("{} = {}", self.identifier(column), self.expr(value))
+                }
+                Assignment::Raw(assignment) => assignment.clone(),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn insert_source(
+        &mut self,
+        statement: &Statement,
+        source: &Subquery,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        let query = &source.0;
+        if query.kind != Some(StatementKind::Select) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Only SELECT queries can be the source of an INSERT",
+            ));
+        }
+        if let Some(arity) = projection_arity(&query.projection) {
+            if !statement.columns.is_empty() && statement.columns.len() != arity {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "INSERT has {} columns but its SELECT returns {}",
+                        statement.columns.len(),
+                        arity
+                    ),
+                ));
+            }
+        }
+        let rendered = self.statement(query)?;
+        if statement.on_conflict.is_some() && self.dialect.requires_where_in_upsert_select() {
+            clauses.push(format!("SELECT * FROM ({}) WHERE true", rendered));
+        } else {
+            clauses.push(rendered);
+        }
+        Ok(())
+    }
+
+    fn upsert_clause(
+        &mut self,
+        on_conflict: &OnConflict,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+        let action = on_conflict
+            .action
+            .as_ref()
+            .ok_or_else(|| invalid("ON CONFLICT requires DO NOTHING or DO UPDATE".to_string()))?;
+        if matches!(action, ConflictAction::Nothing) && !on_conflict.selection.is_empty() {
+            return Err(invalid("DO NOTHING cannot be conditional".to_string()));
+        }
+        match self.dialect.upsert_syntax() {
+            UpsertSyntax::Unsupported => Err(invalid(format!(
+                "Upserts are not supported by the {} dialect",
+                self.dialect.name()
+            ))),
+            // MySQL updates on any unique key, so the conflict target is not rendered.
+            UpsertSyntax::OnDuplicateKeyUpdate => match action {
+                ConflictAction::Nothing => Ok(()),
+                ConflictAction::Update(_) if !on_conflict.selection.is_empty() => {
+                    Err(invalid(format!(
+                        "A conditional DO UPDATE is not supported by the {} dialect",
+                        self.dialect.name()
+                    )))
+                }
+                ConflictAction::Update(assignments) => {
+                    let assignments = self.assignments(assignments);
+                    clauses.push(format!("ON DUPLICATE KEY UPDATE {}", assignments));
+                    Ok(())
+                }
+            },
+            UpsertSyntax::OnConflict => {
+                let target = match &on_conflict.target {
+                    ConflictTarget::None => String::new(),
+                    ConflictTarget::Columns(columns) => format!(" ({})", self.identifiers(columns)),
+                    ConflictTarget::Constraint(constraint) => {
+                        if !self.dialect.supports_conflict_constraint() {
+                            return Err(invalid(format!(
+                                "ON CONFLICT ON CONSTRAINT is not supported by the {} dialect",
+                                self.dialect.name()
+                            )));
+                        }
+                        format!(" ON CONSTRAINT {}", self.identifier(constraint))
+                    }
+                };
+                match action {
+                    ConflictAction::Nothing => {
+                        clauses.push(format!("ON CONFLICT{} DO NOTHING", target));
+                    }
+                    ConflictAction::Update(_) if target.is_empty() => {
+                        return Err(invalid(
+                            "ON CONFLICT DO UPDATE requires a conflict target".to_string(),
+                        ));
+                    }
+                    ConflictAction::Update(assignments) => {
+                        let assignments = self.assignments(assignments);
+                        clauses.push(format!(
+                            "ON CONFLICT{} DO UPDATE SET {}",
+                            target, assignments
+                        ));
+                        if !on_conflict.selection.is_empty() {
+                            let condition = self.condition(&on_conflict.selection);
+                            clauses.push(format!("WHERE {}", condition));
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn with_clause(&mut self, statement: &Statement, clauses: &mut Vec<String>) -> io::Result<()> {
+        if statement.with.is_empty() {
+            return Ok(());
+        }
+        let mut ctes = Vec::new();
+        for cte in &statement.with {
+            let mut definition = self.identifier(&cte.name.name);
+            if !cte.name.columns.is_empty() {
+                definition.push_str(&format!(" ({})", self.identifiers(&cte.name.columns)));
+            }
+            definition.push_str(" AS ");
+            if let Some(materialized) = cte.materialized {
+                if !self.dialect.supports_materialized_cte() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "MATERIALIZED is not supported by the {} dialect",
+                            self.dialect.name()
+                        ),
+                    ));
+                }
+                definition.push_str(if materialized {
+                    "MATERIALIZED "
+                } else {
+                    "NOT MATERIALIZED "
+                });
+            }
+            definition.push_str(&self.subquery(&cte.query));
+            ctes.push(definition);
+        }
+        let with = if statement.recursive && self.dialect.requires_recursive_keyword() {
+            "WITH RECURSIVE"
+        } else {
+            "WITH"
+        };
+        clauses.push(format!("{} {}", with, ctes.join(", ")));
+        Ok(())
+    }
+
+    fn join_clauses(&mut self, statement: &Statement, clauses: &mut Vec<String>) -> io::Result<()> {
+        for join in &statement.joins {
+            if join.kind == JoinKind::Full && !self.dialect.supports_full_join() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "FULL JOIN is not supported by the {} dialect",
+                        self.dialect.name()
+                    ),
+                ));
+            }
+            let table = self.table_ref(&join.table);
+            let constraint = match &join.constraint {
+                JoinConstraint::On(predicates) => {
+                    format!(" ON {}", self.group(predicates, " AND ", "1 = 1"))
+                }
+                JoinConstraint::Using(columns) => {
+                    format!(" USING ({})", self.identifiers(columns))
+                }
+                JoinConstraint::None => String::new(),
+            };
+            clauses.push(format!("{} {}{}", join.kind.keyword(), table, constraint));
+        }
+        Ok(())
+    }
+
+    fn where_clause(&mut self, statement: &Statement, clauses: &mut Vec<String>) {
+        if !statement.selection.is_empty() {
+            clauses.push(format!("WHERE {}", self.condition(&statement.selection)));
+        }
+    }
+
+    fn order_by_clause(&mut self, statement: &Statement, clauses: &mut Vec<String>) {
+        if !statement.order_by.is_empty() {
+            let order_by = statement
+                .order_by
+                .iter()
+                .map(|order_by| match &order_by.direction {
+                    Some(direction) => format!("{} {}", self.expr(&order_by.expr), direction),
+                    None => self.expr(&order_by.expr),
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            clauses.push(format!("ORDER BY {}", order_by));
+        } else if (statement.limit.is_some() || statement.offset.is_some())
+            && self.dialect.requires_order_by_for_offset()
+        {
+            clauses.push("ORDER BY (SELECT NULL)".to_string());
+        }
+    }
+
+    fn returning_clause(
+        &mut self,
+        statement: &Statement,
+        clauses: &mut Vec<String>,
+    ) -> io::Result<()> {
+        if statement.returning.is_empty() {
+            return Ok(());
+        }
+        if !self.dialect.supports_returning() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "RETURNING is not supported by the {} dialect",
+                    self.dialect.name()
+                ),
+            ));
+        }
+        clauses.push(format!("RETURNING {}", self.exprs(&statement.returning)));
+        Ok(())
+    }
+}
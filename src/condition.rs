@@ -0,0 +1,229 @@
+//! Condition expressions for `WHERE`, `HAVING` and `JOIN ON` that nest
+//! with explicit parentheses.
+
+use crate::ast::Predicate;
+use crate::{Expr, Operand, Value};
+
+/// A condition that can be grouped with `Cond::all`, `Cond::any` and `not`.
+#[derive(Clone, Debug)]
+pub struct Cond(pub(crate) Predicate);
+
+impl Cond {
+    /// Builds an `operand operator value` comparison, like `WHERE` does.
+    pub fn new<E, V>(operand: E, operator: &str, result: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        let left = operand.into();
+        let right = result.into_expr();
+        Cond(match operator.trim().to_uppercase().as_str() {
+            // `IS NULL` cannot take a bound value.
+            "IS" if matches!(right, Expr::Value(Value::Null)) => Predicate::IsNull {
+                left,
+                negated: false,
+            },
+            "IS NOT" if matches!(right, Expr::Value(Value::Null)) => Predicate::IsNull {
+                left,
+                negated: true,
+            },
+            _ => Predicate::Compare {
+                left,
+                operator: operator.to_string(),
+                right,
+            },
+        })
+    }
+
+    /// Builds a `column1 operator column2` comparison between two columns.
+    pub fn columns(column1: &str, operator: &str, column2: &str) -> Self {
+        Cond(Predicate::Compare {
+            left: Expr::Column(column1.to_string()),
+            operator: operator.to_string(),
+            right: Expr::Column(column2.to_string()),
+        })
+    }
+
+    /// Builds an `operand IN (values...)` condition.
+    pub fn in_list<E, V>(operand: E, values: &[V]) -> Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone,
+    {
+        Cond(Predicate::InList {
+            left: operand.into(),
+            negated: false,
+            values: values
+                .iter()
+                .map(|value| Expr::Value(value.clone().into()))
+                .collect(),
+        })
+    }
+
+    /// Builds an `operand NOT IN (values...)` condition.
+    pub fn not_in_list<E, V>(operand: E, values: &[V]) -> Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone,
+    {
+        Cond::in_list(operand, values).negate()
+    }
+
+    /// Builds an `operand BETWEEN low AND high` condition.
+    pub fn between<E, V>(operand: E, low: V, high: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond(Predicate::Between {
+            left: operand.into(),
+            negated: false,
+            low: low.into_expr(),
+            high: high.into_expr(),
+        })
+    }
+
+    /// Builds an `operand NOT BETWEEN low AND high` condition.
+    pub fn not_between<E, V>(operand: E, low: V, high: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond::between(operand, low, high).negate()
+    }
+
+    /// Builds an `operand LIKE pattern` condition.
+    pub fn like<E, V>(operand: E, pattern: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond(Predicate::Like {
+            left: operand.into(),
+            negated: false,
+            case_insensitive: false,
+            pattern: pattern.into_expr(),
+            escape: None,
+        })
+    }
+
+    /// Builds an `operand NOT LIKE pattern` condition.
+    pub fn not_like<E, V>(operand: E, pattern: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond::like(operand, pattern).negate()
+    }
+
+    /// Builds a case-insensitive `operand ILIKE pattern` condition, rendered
+    /// as `LOWER(operand) LIKE LOWER(pattern)` outside PostgreSQL.
+    pub fn ilike<E, V>(operand: E, pattern: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        let mut condition = Cond::like(operand, pattern);
+        if let Predicate::Like {
+            case_insensitive, ..
+        } = &mut condition.0
+        {
+            *case_insensitive = true;
+        }
+        condition
+    }
+
+    /// Sets the ESCAPE character of a LIKE or ILIKE condition; other
+    /// conditions are left untouched.
+    pub fn escape(mut self, character: char) -> Self {
+        if let Predicate::Like { escape, .. } = &mut self.0 {
+            *escape = Some(character);
+        }
+        self
+    }
+
+    /// Builds an `operand IS NULL` condition.
+    pub fn is_null<E>(operand: E) -> Self
+    where
+        E: Into<Expr>,
+    {
+        Cond(Predicate::IsNull {
+            left: operand.into(),
+            negated: false,
+        })
+    }
+
+    /// Builds an `operand IS NOT NULL` condition.
+    pub fn is_not_null<E>(operand: E) -> Self
+    where
+        E: Into<Expr>,
+    {
+        Cond::is_null(operand).negate()
+    }
+
+    /// Builds a null-safe `operand IS DISTINCT FROM value` condition.
+    pub fn is_distinct_from<E, V>(operand: E, result: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond(Predicate::IsDistinctFrom {
+            left: operand.into(),
+            negated: false,
+            right: result.into_expr(),
+        })
+    }
+
+    /// Builds a null-safe `operand IS NOT DISTINCT FROM value` condition.
+    pub fn is_not_distinct_from<E, V>(operand: E, result: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        Cond::is_distinct_from(operand, result).negate()
+    }
+
+    /// Flips the built-in negation of the operators that have one.
+    fn negate(mut self) -> Self {
+        match &mut self.0 {
+            Predicate::InList { negated, .. }
+            | Predicate::Between { negated, .. }
+            | Predicate::Like { negated, .. }
+            | Predicate::IsNull { negated, .. }
+            | Predicate::IsDistinctFrom { negated, .. } => *negated = !*negated,
+            _ => return not(self),
+        }
+        self
+    }
+
+    /// Groups the conditions with AND.
+    pub fn all<I>(conditions: I) -> Self
+    where
+        I: IntoIterator<Item = Cond>,
+    {
+        Cond(Predicate::All(
+            conditions
+                .into_iter()
+                .map(|condition| condition.0)
+                .collect(),
+        ))
+    }
+
+    /// Groups the conditions with OR.
+    pub fn any<I>(conditions: I) -> Self
+    where
+        I: IntoIterator<Item = Cond>,
+    {
+        Cond(Predicate::Any(
+            conditions
+                .into_iter()
+                .map(|condition| condition.0)
+                .collect(),
+        ))
+    }
+}
+
+/// Negates a condition.
+pub fn not(condition: Cond) -> Cond {
+    Cond(Predicate::Not(Box::new(condition.0)))
+}
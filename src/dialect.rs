@@ -0,0 +1,433 @@
+use std::fmt::Debug;
+
+use crate::{sql_injection_prevention, Value};
+
+/// How a dialect spells an INSERT that updates or skips conflicting rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UpsertSyntax {
+    /// `ON CONFLICT (...) DO NOTHING | DO UPDATE SET ...`
+    OnConflict,
+    /// `INSERT IGNORE` and `ON DUPLICATE KEY UPDATE ...`
+    OnDuplicateKeyUpdate,
+    Unsupported,
+}
+
+/// Controls how a `SqlQueryBuilder` renders the parts of a query that differ
+/// between databases.
+pub trait Dialect: Clone + Debug + Default {
+    /// Name of the dialect, used in error messages.
+    fn name(&self) -> &'static str;
+    /// Renders a string as a quoted SQL literal.
+    fn quote_string(&self, value: &str) -> String;
+    /// Renders a (possibly qualified) identifier.
+    fn quote_identifier(&self, identifier: &str) -> String;
+    /// Renders the placeholder of the bound value at `index`, starting from 1.
+    fn placeholder(&self, index: usize) -> String;
+    /// Renders the pagination clause.
+    fn limit_offset(&self, limit: Option<u32>, offset: Option<u32>) -> String {
+        let mut clause = String::new();
+        if let Some(limit) = limit {
+            clause.push_str(&format!("LIMIT {}", limit));
+        }
+        if let Some(offset) = offset {
+            if !clause.is_empty() {
+                clause.push(' ');
+            }
+            clause.push_str(&format!("OFFSET {}", offset));
+        }
+        clause
+    }
+    /// Whether pagination needs an ORDER BY clause to be valid.
+    fn requires_order_by_for_offset(&self) -> bool {
+        false
+    }
+    /// Whether the dialect supports the RETURNING clause.
+    fn supports_returning(&self) -> bool {
+        true
+    }
+    /// Whether the dialect supports FULL OUTER JOIN.
+    fn supports_full_join(&self) -> bool {
+        true
+    }
+    /// Whether common table expressions accept MATERIALIZED hints.
+    fn supports_materialized_cte(&self) -> bool {
+        true
+    }
+    /// Whether recursive common table expressions need WITH RECURSIVE.
+    fn requires_recursive_keyword(&self) -> bool {
+        true
+    }
+    /// Whether the operands of UNION, INTERSECT and EXCEPT may be
+    /// parenthesised.
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        true
+    }
+    /// Whether the dialect has a case-insensitive ILIKE operator. Without
+    /// it, both sides of the comparison are lowered instead.
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+    /// Renders a null-safe comparison, `IS [NOT] DISTINCT FROM`.
+    fn is_distinct_from(&self, left: &str, right: &str, negated: bool) -> String {
+        let operator = if negated {
+            "IS NOT DISTINCT FROM"
+        } else {
+            "IS DISTINCT FROM"
+        };
+        format!("{} {} {}", left, operator, right)
+    }
+    /// Which upsert syntax the dialect uses.
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnConflict
+    }
+    /// Whether ON CONFLICT accepts a constraint name as its target.
+    fn supports_conflict_constraint(&self) -> bool {
+        true
+    }
+    /// Renders a reference to the value a conflicting INSERT proposed for
+    /// an already quoted column.
+    fn excluded(&self, column: &str) -> String {
+        format!("EXCLUDED.{}", column)
+    }
+    /// Whether an UPDATE or DELETE lists the other tables it reads next to
+    /// its target, as in `UPDATE t, other SET ...` and `DELETE t FROM t, other`.
+    fn tables_before_set(&self) -> bool {
+        false
+    }
+    /// The keyword introducing the other tables a DELETE reads, if the
+    /// dialect can read any.
+    fn delete_using_keyword(&self) -> Option<&'static str> {
+        Some("USING")
+    }
+    /// Whether the SELECT of an `INSERT ... SELECT` needs a WHERE clause for
+    /// a following ON CONFLICT to parse.
+    fn requires_where_in_upsert_select(&self) -> bool {
+        false
+    }
+    /// Whether `DEFAULT` may stand for a value in the rows of an INSERT.
+    /// Without it, `NULL` is inserted instead.
+    fn supports_default_values(&self) -> bool {
+        true
+    }
+    /// Maximum number of values one statement may bind.
+    fn max_bind_params(&self) -> usize {
+        65535
+    }
+    /// Maximum number of rows in the VALUES list of one INSERT, if any.
+    fn max_insert_rows(&self) -> Option<usize> {
+        None
+    }
+    /// Renders a boolean literal.
+    fn render_bool(&self, value: bool) -> String {
+        if value { "TRUE" } else { "FALSE" }.to_string()
+    }
+    /// Renders a binary literal.
+    fn render_bytes(&self, value: &[u8]) -> String {
+        format!("X'{}'", hex(value))
+    }
+    /// Renders a value as an inlined SQL literal.
+    fn render_value(&self, value: &Value) -> String {
+        match value {
+            Value::Null => "NULL".to_string(),
+            Value::Bool(bool) => self.render_bool(*bool),
+            Value::Int(int) => int.to_string(),
+            Value::UInt(uint) => uint.to_string(),
+            Value::Float(float) => float.to_string(),
+            Value::Text(text) => self.quote_string(text),
+            Value::Bytes(bytes) => self.render_bytes(bytes),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Quotes every part of a dotted identifier, leaving `*` untouched.
+/// Numeric literals are passed through as they are; anything else that is
+/// not a column, such as an expression, has to go through `functions::raw`.
+fn quote_parts(identifier: &str, open: char, close: char) -> String {
+    if is_numeric_literal(identifier) {
+        return identifier.to_string();
+    }
+    identifier
+        .split('.')
+        .map(|part| {
+            if part == "*" {
+                part.to_string()
+            } else {
+                let escaped = part.replace(close, &format!("{}{}", close, close));
+                format!("{}{}{}", open, escaped, close)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+fn is_numeric_literal(identifier: &str) -> bool {
+    identifier.starts_with(|c: char| c.is_ascii_digit())
+        && identifier.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && identifier.parse::<f64>().is_ok()
+}
+
+/// The dialect the builder has always produced: backslash escaping,
+/// unquoted identifiers, `$1` placeholders, `LIMIT`/`OFFSET` and `RETURNING`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Generic;
+
+impl Dialect for Generic {
+    fn name(&self) -> &'static str {
+        "Generic"
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        format!("'{}'", sql_injection_prevention(value))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        sql_injection_prevention(identifier)
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    fn name(&self) -> &'static str {
+        "PostgreSQL"
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_parts(identifier, '"', '"')
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+
+    fn supports_ilike(&self) -> bool {
+        true
+    }
+
+    fn render_bytes(&self, value: &[u8]) -> String {
+        format!("'\\x{}'::bytea", hex(value))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MySql;
+
+impl Dialect for MySql {
+    fn name(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        let mut quoted = String::from("'");
+        for c in value.chars() {
+            match c {
+                '\\' => quoted.push_str("\\\\"),
+                '\'' => quoted.push_str("\\'"),
+                '\0' => quoted.push_str("\\0"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\x1a' => quoted.push_str("\\Z"),
+                _ => quoted.push(c),
+            }
+        }
+        quoted.push('\'');
+        quoted
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_parts(identifier, '`', '`')
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn limit_offset(&self, limit: Option<u32>, offset: Option<u32>) -> String {
+        match (limit, offset) {
+            // MySQL has no OFFSET without LIMIT.
+            (None, Some(offset)) => format!("LIMIT 18446744073709551615 OFFSET {}", offset),
+            (limit, offset) => Generic.limit_offset(limit, offset),
+        }
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn supports_full_join(&self) -> bool {
+        false
+    }
+
+    fn supports_materialized_cte(&self) -> bool {
+        false
+    }
+
+    fn is_distinct_from(&self, left: &str, right: &str, negated: bool) -> String {
+        if negated {
+            format!("{} <=> {}", left, right)
+        } else {
+            format!("NOT ({} <=> {})", left, right)
+        }
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::OnDuplicateKeyUpdate
+    }
+
+    fn tables_before_set(&self) -> bool {
+        true
+    }
+
+    fn excluded(&self, column: &str) -> String {
+        format!("VALUES({})", column)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    fn name(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_parts(identifier, '"', '"')
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn limit_offset(&self, limit: Option<u32>, offset: Option<u32>) -> String {
+        match (limit, offset) {
+            // SQLite has no OFFSET without LIMIT.
+            (None, Some(offset)) => format!("LIMIT -1 OFFSET {}", offset),
+            (limit, offset) => Generic.limit_offset(limit, offset),
+        }
+    }
+
+    fn supports_parenthesized_set_operands(&self) -> bool {
+        false
+    }
+
+    fn is_distinct_from(&self, left: &str, right: &str, negated: bool) -> String {
+        let operator = if negated { "IS" } else { "IS NOT" };
+        format!("{} {} {}", left, operator, right)
+    }
+
+    fn supports_conflict_constraint(&self) -> bool {
+        false
+    }
+
+    fn requires_where_in_upsert_select(&self) -> bool {
+        true
+    }
+
+    fn delete_using_keyword(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn supports_default_values(&self) -> bool {
+        false
+    }
+
+    fn max_bind_params(&self) -> usize {
+        32766
+    }
+
+    fn render_bool(&self, value: bool) -> String {
+        if value { "1" } else { "0" }.to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SqlServer;
+
+impl Dialect for SqlServer {
+    fn name(&self) -> &'static str {
+        "SQL Server"
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        format!("N'{}'", value.replace('\'', "''"))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        quote_parts(identifier, '[', ']')
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("@p{}", index)
+    }
+
+    fn limit_offset(&self, limit: Option<u32>, offset: Option<u32>) -> String {
+        match (limit, offset) {
+            (None, None) => String::new(),
+            (limit, offset) => {
+                let mut clause = format!("OFFSET {} ROWS", offset.unwrap_or(0));
+                if let Some(limit) = limit {
+                    clause.push_str(&format!(" FETCH NEXT {} ROWS ONLY", limit));
+                }
+                clause
+            }
+        }
+    }
+
+    fn requires_order_by_for_offset(&self) -> bool {
+        true
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn supports_materialized_cte(&self) -> bool {
+        false
+    }
+
+    fn requires_recursive_keyword(&self) -> bool {
+        false
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::Unsupported
+    }
+
+    fn delete_using_keyword(&self) -> Option<&'static str> {
+        Some("FROM")
+    }
+
+    fn max_bind_params(&self) -> usize {
+        2100
+    }
+
+    fn max_insert_rows(&self) -> Option<usize> {
+        Some(1000)
+    }
+
+    fn render_bool(&self, value: bool) -> String {
+        if value { "1" } else { "0" }.to_string()
+    }
+
+    fn render_bytes(&self, value: &[u8]) -> String {
+        format!("0x{}", hex(value))
+    }
+}
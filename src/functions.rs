@@ -0,0 +1,58 @@
+//! Expression helpers for the select list and for both sides of predicates.
+#![allow(non_snake_case)]
+
+use crate::{Expr, Value};
+
+/// References a column, e.g. `WHERE("updated_at", ">", col("created_at"))`.
+pub fn col(column: &str) -> Expr {
+    Expr::Column(column.to_string())
+}
+
+/// A literal value, bound like the right-hand side of `WHERE`.
+pub fn val<V: Into<Value>>(value: V) -> Expr {
+    Expr::Value(value.into())
+}
+
+/// An arbitrary SQL expression, inserted verbatim without any escaping.
+pub fn raw(sql: &str) -> Expr {
+    Expr::Raw(sql.to_string())
+}
+
+/// The value a conflicting INSERT proposed for a column, for `DO_UPDATE_SET`:
+/// `EXCLUDED.column`, or `VALUES(column)` in MySQL.
+pub fn excluded(column: &str) -> Expr {
+    Expr::Excluded(column.to_string())
+}
+
+fn aggregate<E: Into<Expr>>(function: &'static str, argument: E) -> Expr {
+    Expr::Aggregate {
+        function,
+        distinct: false,
+        argument: Box::new(argument.into()),
+    }
+}
+
+/// Builds a `COUNT(argument)` expression.
+pub fn COUNT<E: Into<Expr>>(argument: E) -> Expr {
+    aggregate("COUNT", argument)
+}
+
+/// Builds a `SUM(argument)` expression.
+pub fn SUM<E: Into<Expr>>(argument: E) -> Expr {
+    aggregate("SUM", argument)
+}
+
+/// Builds an `AVG(argument)` expression.
+pub fn AVG<E: Into<Expr>>(argument: E) -> Expr {
+    aggregate("AVG", argument)
+}
+
+/// Builds a `MIN(argument)` expression.
+pub fn MIN<E: Into<Expr>>(argument: E) -> Expr {
+    aggregate("MIN", argument)
+}
+
+/// Builds a `MAX(argument)` expression.
+pub fn MAX<E: Into<Expr>>(argument: E) -> Expr {
+    aggregate("MAX", argument)
+}
@@ -1,12 +1,35 @@
-use std::{
-    collections::HashMap,
-    io::{self},
-};
+use std::io::{self};
+
+extern crate self as rust_query_builder;
 
+mod ast;
+pub mod condition;
+pub mod dialect;
+pub mod functions;
 pub mod iterate_struct;
-use struct_iterable::Iterable;
+pub mod row;
+pub mod schema;
+pub mod serialize;
+pub mod stage;
+pub mod table;
+pub mod value;
+pub use ast::{CteName, Expr, JoinKind, Operand, Subquery, TableRef};
+pub use condition::{not, Cond};
+pub use dialect::{Dialect, Generic, MySql, Postgres, SqlServer, Sqlite, UpsertSyntax};
+pub use functions::{col, excluded, raw, val, AVG, COUNT, MAX, MIN, SUM};
+pub use row::{FromRow, FromSqlValue, Row};
+pub use rust_query_builder_derive::{FromRow, Table};
+pub use serialize::Serialized;
+pub use stage::Query;
+pub use struct_iterable::Iterable;
+pub use table::{Table, ToColumns};
+pub use value::{ToSqlValue, Value};
 
-use crate::iterate_struct::iterate_struct;
+use crate::ast::{
+    is_predicate_operator, Assignment, ConditionItem, ConditionTarget, ConflictAction,
+    ConflictTarget, Connector, Cte, Join, JoinConstraint, OnConflict, OrderBy, Predicate, Renderer,
+    SetOperation, SetOperator, Statement, StatementKind,
+};
 
 fn sql_injection_prevention(query: &str) -> String {
     let mut sanitized_query = String::new();
@@ -46,425 +69,1248 @@ fn sql_injection_prevention(query: &str) -> String {
     sanitized_query
 }
 
+/// Operators accepted when comparing columns in a join condition.
+const COMPARISON_OPERATORS: [&str; 7] = ["=", "<>", "!=", "<", "<=", ">", ">="];
+
 #[allow(non_snake_case)]
 pub trait QueryBuilder: Clone + Default + Sized {
     /// Constructs a new `SqlQueryBuilder`.
     fn new() -> Self;
     /// Constructs a new `SqlQueryBuilder` with a table.
-    fn table(table: &str) -> Self;
+    fn table<T>(table: T) -> Self
+    where
+        T: AsRef<str>;
+    /// Constructs a new `SqlQueryBuilder` with the table of a `Table` struct.
+    fn table_of<T>() -> Self
+    where
+        T: Table;
+    /// ANDs a grouped condition onto the current WHERE or HAVING clause.
+    fn AND_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>;
     /// Adds an AND NOT clause to the SQL query.
-    fn AND_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
+    fn AND_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
     /// Adds an AND clause to the SQL query.
-    fn AND(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
-    /// Adds a FROM clause to the SQL query.
-    fn FROM(&mut self, table: &str) -> &mut Self;
+    fn AND<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Adds a CROSS JOIN clause to the SQL query.
+    fn CROSS_JOIN<T>(&mut self, table: T) -> &mut Self
+    where
+        T: Into<TableRef>;
+    /// Adds a DELETE clause to the SQL query.
+    fn DELETE(&mut self) -> &mut Self;
+    /// Adds a DELETE clause filtered by the non-null fields of an iterable.
+    fn DELETE_BY<T>(&mut self, columns: &T) -> &mut Self
+    where
+        T: ToColumns;
+    /// Skips the rows of an INSERT that conflict with existing ones.
+    fn DO_NOTHING(&mut self) -> &mut Self;
+    /// Updates the given columns of conflicting rows with the values the
+    /// INSERT proposed. A following DO_UPDATE_WHERE makes the update conditional.
+    fn DO_UPDATE(&mut self, columns: &[&str]) -> &mut Self;
+    /// Updates conflicting rows with the given values or expressions, see
+    /// `excluded`. A following DO_UPDATE_WHERE makes the update conditional.
+    fn DO_UPDATE_SET<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Operand + Clone;
+    /// Only updates the conflicting rows matching the condition. Subsequent
+    /// AND and OR calls extend it until the next WHERE.
+    fn DO_UPDATE_WHERE<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Only updates the conflicting rows matching a grouped condition.
+    fn DO_UPDATE_WHERE_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>;
+    /// Removes the rows of another SELECT from the result of this one.
+    fn EXCEPT(&mut self, query: &Self) -> &mut Self;
+    /// Adds a FROM clause to the SQL query. Accepts `"table"` or `("table", "alias")`.
+    fn FROM<T>(&mut self, table: T) -> &mut Self
+    where
+        T: Into<TableRef>;
+    /// Adds a FULL JOIN clause to the SQL query.
+    fn FULL_JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>;
+    /// Adds a GROUP BY clause to the SQL query.
+    fn GROUP_BY<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone;
+    /// Adds a HAVING clause with a grouped condition to the SQL query.
+    fn HAVING_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>;
+    /// Adds a HAVING clause to the SQL query. Subsequent AND and OR calls
+    /// extend it until the next WHERE.
+    fn HAVING<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
     /// Inserts a slice of columns and values into the SQL query.
-    fn INSERT_AS_SLICE(&mut self, columns_and_values: &[(&str, &str)]) -> &mut Self;
-    /// Inserts an iterable of columns into the SQL query.
+    fn INSERT_AS_SLICE<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Into<Value> + Clone;
+    /// Inserts the rows returned by another SELECT into the given columns.
+    fn INSERT_FROM_SELECT(&mut self, columns: &[&str], query: &Self) -> &mut Self;
+    /// Inserts an iterable of columns into the SQL query. `None` fields are
+    /// inserted as `NULL`, columns the iterable leaves out get their default.
     fn INSERT<T>(&mut self, columns: &T) -> &mut Self
     where
-        T: Iterable;
+        T: ToColumns;
+    /// Inserts one row per iterable. As with `INSERT`, `None` fields are
+    /// inserted as `NULL`; a column some rows leave out, such as a
+    /// `#[column(default)]` field, is `DEFAULT` in those rows, or `NULL`
+    /// where the dialect has no `DEFAULT` values.
+    fn INSERT_MANY<T>(&mut self, rows: &[T]) -> &mut Self
+    where
+        T: ToColumns;
+    /// Keeps only the rows also returned by another SELECT.
+    fn INTERSECT(&mut self, query: &Self) -> &mut Self;
     /// Adds a JOIN clause to the SQL query.
-    fn JOIN(&mut self, table: &str, column1: &str, operator: &str, column2: &str) -> &mut Self;
+    fn JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>;
+    /// Adds a join of any kind whose ON clause is a grouped condition.
+    fn JOIN_COND<T, C>(&mut self, kind: JoinKind, table: T, on: C) -> &mut Self
+    where
+        T: Into<TableRef>,
+        C: Into<Cond>;
+    /// Adds a join of any kind whose ON condition ANDs all the given predicates.
+    fn JOIN_ON<T>(&mut self, kind: JoinKind, table: T, on: &[(&str, &str, &str)]) -> &mut Self
+    where
+        T: Into<TableRef>;
+    /// Adds a join of any kind with a USING (columns) clause.
+    fn JOIN_USING<T>(&mut self, kind: JoinKind, table: T, columns: &[&str]) -> &mut Self
+    where
+        T: Into<TableRef>;
+    /// Adds a LEFT JOIN clause to the SQL query.
+    fn LEFT_JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>;
     /// Adds a LIMIT clause to the SQL query.
     fn LIMIT(&mut self, limit: u32) -> &mut Self;
     /// Adds an OFFSET clause to the SQL query.
     fn OFFSET(&mut self, limit: u32) -> &mut Self;
+    /// Sets the columns whose unique constraint an INSERT may conflict with.
+    fn ON_CONFLICT(&mut self, columns: &[&str]) -> &mut Self;
+    /// Sets the named constraint an INSERT may conflict with.
+    fn ON_CONFLICT_ON_CONSTRAINT(&mut self, constraint: &str) -> &mut Self;
+    /// ORs a grouped condition onto the current WHERE or HAVING clause.
+    fn OR_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>;
     /// Adds an OR NOT clause to the SQL query.
-    fn OR_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
+    fn OR_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
     /// Adds an OR clause to the SQL query.
-    fn OR(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
+    fn OR<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
     /// Adds an ORDER BY clause to the SQL query.
-    fn ORDER_BY(&mut self, columns: &[&str]) -> &mut Self;
+    fn ORDER_BY<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone;
     /// Adds a RETURNING clause to the SQL query.
-    fn RETURNING(&mut self, columns: &[&str]) -> &mut Self;
+    fn RETURNING<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone;
+    /// Adds a RIGHT JOIN clause to the SQL query.
+    fn RIGHT_JOIN<T, L, R>(
+        &mut self,
+        table: T,
+        column1: L,
+        operator: &str,
+        column2: R,
+    ) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>;
     /// Adds a SELECT DISTINCT clause to the SQL query.
-    fn SELECT_DISTINCT(&mut self, columns: &[&str]) -> &mut Self;
+    fn SELECT_DISTINCT<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone;
     /// Adds a SELECT clause to the SQL query.
-    fn SELECT(&mut self, columns: &[&str]) -> &mut Self;
+    fn SELECT<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone;
     /// Adds a SET clause to the SQL query.
     fn SET(&mut self, columns: &[&str]) -> &mut Self;
-    /// Adds a UPDATE_AS_SLICE clause to the SQL query.
-    fn UPDATE_AS_SLICE(&mut self, columns_and_values: &[(&str, &str)]) -> &mut Self;
+    /// Combines the result with another SELECT, removing duplicate rows.
+    fn UNION(&mut self, query: &Self) -> &mut Self;
+    /// Combines the result with another SELECT, keeping duplicate rows.
+    fn UNION_ALL(&mut self, query: &Self) -> &mut Self;
+    /// Adds a UPDATE_AS_SLICE clause to the SQL query. Values may reference
+    /// columns, e.g. of a table joined with FROM or JOIN.
+    fn UPDATE_AS_SLICE<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Operand + Clone;
+    /// Adds tables a DELETE joins with to find the rows to delete.
+    fn USING<T>(&mut self, tables: &[T]) -> &mut Self
+    where
+        T: Into<TableRef> + Clone;
     /// Adds a UPDATE clause to the SQL query.
     fn UPDATE<T>(&mut self, columns: &T) -> &mut Self
     where
-        T: Iterable;
+        T: ToColumns;
     fn WHERE_AND<T>(&mut self, columns: &T) -> &mut Self
     where
-        T: Iterable;
-    fn WHERE_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
-    fn WHERE(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self;
+        T: ToColumns;
+    /// Adds a WHERE clause with a grouped condition to the SQL query.
+    fn WHERE_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>;
+    /// Adds a WHERE ... BETWEEN low AND high clause to the SQL query.
+    fn WHERE_BETWEEN<E, V>(&mut self, operand: E, low: V, high: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Adds a WHERE EXISTS (subquery) clause to the SQL query.
+    fn WHERE_EXISTS(&mut self, subquery: &Self) -> &mut Self;
+    /// Adds a WHERE ... IN (subquery) clause to the SQL query.
+    fn WHERE_IN<E>(&mut self, operand: E, subquery: &Self) -> &mut Self
+    where
+        E: Into<Expr>;
+    /// Adds a WHERE ... IN (values) clause to the SQL query.
+    fn WHERE_IN_LIST<E, V>(&mut self, operand: E, values: &[V]) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone;
+    /// Adds a WHERE ... LIKE pattern clause to the SQL query.
+    fn WHERE_LIKE<E, V>(&mut self, operand: E, pattern: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Adds a WHERE NOT EXISTS (subquery) clause to the SQL query.
+    fn WHERE_NOT_EXISTS(&mut self, subquery: &Self) -> &mut Self;
+    /// Adds a WHERE ... NOT IN (subquery) clause to the SQL query.
+    fn WHERE_NOT_IN<E>(&mut self, operand: E, subquery: &Self) -> &mut Self
+    where
+        E: Into<Expr>;
+    /// Adds a WHERE ... NOT IN (values) clause to the SQL query.
+    fn WHERE_NOT_IN_LIST<E, V>(&mut self, operand: E, values: &[V]) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone;
+    /// Adds a WHERE ... IS NOT NULL clause to the SQL query.
+    fn WHERE_NOT_NULL<E>(&mut self, operand: E) -> &mut Self
+    where
+        E: Into<Expr>;
+    fn WHERE_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Adds a WHERE ... IS NULL clause to the SQL query.
+    fn WHERE_NULL<E>(&mut self, operand: E) -> &mut Self
+    where
+        E: Into<Expr>;
+    fn WHERE<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand;
+    /// Adds a common table expression to the WITH clause of the SQL query.
+    /// Accepts `"name"` or `("name", &["column", ...])`.
+    fn WITH<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>;
+    /// Adds a common table expression with a MATERIALIZED hint.
+    fn WITH_MATERIALIZED<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>;
+    /// Adds a common table expression with a NOT MATERIALIZED hint.
+    fn WITH_NOT_MATERIALIZED<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>;
+    /// Adds a common table expression and turns the clause into WITH RECURSIVE.
+    fn WITH_RECURSIVE<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>;
     fn to_string(&self) -> io::Result<String>;
     fn build(&self) -> io::Result<String>;
+    /// Builds the SQL query with the dialect's placeholders instead of inlined
+    /// values, returning it together with the values to bind, in order.
+    fn build_with_params(&self) -> io::Result<(String, Vec<Value>)>;
+    /// Like `build_with_params`, but splits a multi-row INSERT into as many
+    /// statements as the dialect's bind-parameter and row limits require.
+    fn build_batches(&self) -> io::Result<Vec<(String, Vec<Value>)>>;
 }
 
 #[derive(Clone, Debug, Default)]
-pub struct SqlQueryBuilder {
-    query: String,
-    table: String,
-    error_message: Option<String>,
+pub struct SqlQueryBuilder<D: Dialect = Generic> {
+    statement: Statement,
+    dialect: D,
+    condition_target: ConditionTarget,
+}
+
+impl SqlQueryBuilder {
+    /// Constructs a new `SqlQueryBuilder` for the `Generic` dialect.
+    /// Use `SqlQueryBuilder::<D>::new()` to target another dialect.
+    pub fn new() -> Self {
+        <Self as QueryBuilder>::new()
+    }
+
+    /// Constructs a new `SqlQueryBuilder` with a table for the `Generic` dialect.
+    pub fn table<T>(table: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        <Self as QueryBuilder>::table(table)
+    }
+
+    /// Constructs a new `SqlQueryBuilder` with the table of a `Table` struct
+    /// for the `Generic` dialect.
+    pub fn table_of<T>() -> Self
+    where
+        T: Table,
+    {
+        <Self as QueryBuilder>::table_of::<T>()
+    }
+}
+
+impl<D: Dialect> SqlQueryBuilder<D> {
+    fn push_predicate(
+        &mut self,
+        connector: Connector,
+        negated: bool,
+        operand: Expr,
+        operator: &str,
+        result: Expr,
+    ) {
+        if !is_predicate_operator(operator) {
+            self.statement.error_message = Some(format!("Invalid operator: {}", operator));
+        }
+        let Cond(predicate) = Cond::new(operand, operator, result);
+        let item = ConditionItem {
+            connector,
+            negated,
+            predicate,
+        };
+        self.push_condition(item);
+    }
+
+    fn push_cond(&mut self, connector: Connector, condition: Cond) {
+        self.push_condition(ConditionItem {
+            connector,
+            negated: false,
+            predicate: condition.0,
+        });
+    }
+
+    /// Returns the columns of a struct, recording the error of a field that
+    /// has no SQL value so that `build` returns it.
+    fn read_columns(&mut self, columns: io::Result<Vec<(String, Value)>>) -> Vec<(String, Value)> {
+        columns.unwrap_or_else(|error| {
+            self.statement.error_message = Some(error.to_string());
+            Vec::new()
+        })
+    }
+
+    fn on_conflict(&mut self) -> &mut OnConflict {
+        self.statement.on_conflict.get_or_insert(OnConflict {
+            target: ConflictTarget::None,
+            action: None,
+            selection: Vec::new(),
+        })
+    }
+
+    fn push_cte(&mut self, name: CteName, materialized: Option<bool>, query: &Self) {
+        self.statement.with.push(Cte {
+            name,
+            materialized,
+            query: query.into(),
+        });
+    }
+
+    fn push_set_operation(&mut self, operator: SetOperator, query: &Self) {
+        self.statement.set_operations.push(SetOperation {
+            operator,
+            query: query.into(),
+        });
+    }
+
+    fn push_condition(&mut self, item: ConditionItem) {
+        match self.condition_target {
+            ConditionTarget::Where => self.statement.selection.push(item),
+            ConditionTarget::Having => self.statement.having.push(item),
+            ConditionTarget::ConflictUpdate => self.on_conflict().selection.push(item),
+        }
+    }
+
+    fn push_join(&mut self, kind: JoinKind, table: TableRef, on: Vec<(Expr, &str, Expr)>) {
+        let mut predicates = Vec::new();
+        for (left, operator, right) in on {
+            if !COMPARISON_OPERATORS.contains(&operator.to_uppercase().as_str()) {
+                self.statement.error_message = Some(format!("Invalid join operator: {}", operator));
+            }
+            predicates.push(Predicate::Compare {
+                left,
+                operator: operator.to_string(),
+                right,
+            });
+        }
+        self.statement.joins.push(Join {
+            kind,
+            table,
+            constraint: JoinConstraint::On(predicates),
+        });
+    }
+
+    /// Renders the query, inlining the values when `params` is `None` and
+    /// collecting them behind placeholders otherwise.
+    fn render(&self, params: Option<&mut Vec<Value>>) -> io::Result<String> {
+        let query = Renderer::new(&self.dialect, params).statement(&self.statement)?;
+        Ok(query + ";")
+    }
+}
+
+/// Number of values a row of an INSERT binds.
+fn bound_values(row: &[Expr]) -> usize {
+    row.iter()
+        .filter(|expr| matches!(expr, Expr::Value(_)))
+        .count()
+}
+
+impl<D: Dialect> From<&SqlQueryBuilder<D>> for Subquery {
+    fn from(builder: &SqlQueryBuilder<D>) -> Self {
+        Subquery(Box::new(builder.statement.clone()))
+    }
+}
+
+impl<D: Dialect> From<&SqlQueryBuilder<D>> for Expr {
+    fn from(builder: &SqlQueryBuilder<D>) -> Self {
+        Expr::Subquery(builder.into())
+    }
+}
+
+impl<D: Dialect> From<(&SqlQueryBuilder<D>, &str)> for TableRef {
+    fn from((builder, alias): (&SqlQueryBuilder<D>, &str)) -> Self {
+        (Subquery::from(builder), alias).into()
+    }
 }
 
 /// Represents a SQL Query Builder.
 #[allow(non_snake_case)]
-impl QueryBuilder for SqlQueryBuilder {
+impl<D: Dialect> QueryBuilder for SqlQueryBuilder<D> {
     fn new() -> Self {
         Self {
-            query: String::new(),
-            table: String::new(),
-            error_message: None,
+            statement: Statement::default(),
+            dialect: D::default(),
+            condition_target: ConditionTarget::Where,
         }
     }
 
-    fn table(table: &str) -> Self {
-        let table = sql_injection_prevention(&table.to_string());
-        Self {
-            query: String::new(),
-            table,
-            error_message: None,
-        }
+    fn table<T>(table: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let mut builder = Self::new();
+        builder.statement.table = table.as_ref().to_string();
+        builder
     }
 
-    fn AND_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("AND NOT ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
-        self
+    fn table_of<T>() -> Self
+    where
+        T: Table,
+    {
+        Self::table(T::TABLE_NAME)
     }
 
-    fn AND(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("AND ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
+    fn AND_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>,
+    {
+        self.push_cond(Connector::And, condition.into());
         self
     }
 
-    fn FROM(&mut self, table: &str) -> &mut Self {
-        let table = sql_injection_prevention(&table.to_string());
-        self.query.push_str("FROM ");
-        self.query.push_str(&table);
-        self.query.push_str(" ");
+    fn AND_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.push_predicate(
+            Connector::And,
+            true,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
         self
     }
 
-    fn INSERT_AS_SLICE(&mut self, columns_and_values: &[(&str, &str)]) -> &mut Self {
-        let mut columns = String::new();
-        let mut values = String::new();
-        for (column, value) in columns_and_values {
-            columns.push_str(&sql_injection_prevention(&column.to_string()));
-            columns.push_str(", ");
-            values.push_str("'");
-            values.push_str(&sql_injection_prevention(&value.to_string()));
-            values.push_str("', ");
-        }
-        // let table = sql_injection_prevention(&table.to_string());
-        self.query.push_str("INSERT INTO ");
-        self.query.push_str(&self.table);
-        self.query.push_str(" ");
-        self.query.push_str("(");
-        self.query.push_str(&columns[..columns.len() - 2]);
-        self.query.push_str(") ");
-        self.query.push_str("VALUES ");
-        self.query.push_str("(");
-        self.query.push_str(&values[..values.len() - 2]);
-        self.query.push_str(") ");
+    fn AND<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.push_predicate(
+            Connector::And,
+            false,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
         self
     }
 
-    fn INSERT<T>(&mut self, columns: &T) -> &mut Self
+    fn CROSS_JOIN<T>(&mut self, table: T) -> &mut Self
     where
-        T: Iterable,
+        T: Into<TableRef>,
     {
-        let iterable: HashMap<String, String> = iterate_struct(columns);
+        self.statement.joins.push(Join {
+            kind: JoinKind::Cross,
+            table: table.into(),
+            constraint: JoinConstraint::None,
+        });
+        self
+    }
 
-        let mut columns = String::new();
-        let mut values = String::new();
+    fn DELETE(&mut self) -> &mut Self {
+        self.statement.kind = Some(StatementKind::Delete);
+        self
+    }
 
-        for (column, value) in &iterable {
-            columns.push_str(&sql_injection_prevention(&column.to_string()));
-            columns.push_str(", ");
-            values.push_str("'");
-            values.push_str(&sql_injection_prevention(&value.to_string()));
-            values.push_str("', ");
+    fn DELETE_BY<T>(&mut self, columns: &T) -> &mut Self
+    where
+        T: ToColumns,
+    {
+        if let Ok(iterable) = columns.to_columns() {
+            if iterable.iter().all(|(_, value)| value.is_null()) {
+                panic!("No columns and values provided");
+            }
         }
 
-        self.query.push_str("INSERT INTO ");
-        self.query.push_str(&self.table);
-        self.query.push_str(" ");
-        self.query.push_str("(");
-        self.query.push_str(&columns[..columns.len() - 2]);
-        self.query.push_str(") ");
-        self.query.push_str("VALUES ");
-        self.query.push_str("(");
-        self.query.push_str(&values[..values.len() - 2]);
-        self.query.push_str(") ");
+        self.DELETE().WHERE_AND(columns)
+    }
+
+    fn DO_NOTHING(&mut self) -> &mut Self {
+        self.on_conflict().action = Some(ConflictAction::Nothing);
         self
     }
 
-    fn JOIN(&mut self, table: &str, column1: &str, operator: &str, column2: &str) -> &mut Self {
-        let table = sql_injection_prevention(table);
-        let column1 = sql_injection_prevention(column1);
-        let column2 = sql_injection_prevention(column2);
-        let join_clause = format!("JOIN {} ON {} {} {}", table, column1, operator, column2);
-        self.query.push_str(&join_clause);
-        self.query.push(' ');
+    fn DO_UPDATE(&mut self, columns: &[&str]) -> &mut Self {
+        let assignments = columns
+            .iter()
+            .map(|column| Assignment::Set(column.to_string(), excluded(column)))
+            .collect();
+        self.on_conflict().action = Some(ConflictAction::Update(assignments));
         self
     }
 
-    fn LIMIT(&mut self, limit: u32) -> &mut Self {
-        self.query.push_str("LIMIT ");
-        self.query.push_str(&limit.to_string());
-        self.query.push_str(" ");
+    fn DO_UPDATE_SET<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Operand + Clone,
+    {
+        let assignments = columns_and_values
+            .iter()
+            .map(|(column, value)| Assignment::Set(column.to_string(), value.clone().into_expr()))
+            .collect();
+        self.on_conflict().action = Some(ConflictAction::Update(assignments));
         self
     }
 
-    fn OFFSET(&mut self, limit: u32) -> &mut Self {
-        self.query.push_str("OFFSET ");
-        self.query.push_str(&limit.to_string());
-        self.query.push_str(" ");
+    fn DO_UPDATE_WHERE<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.condition_target = ConditionTarget::ConflictUpdate;
+        self.push_predicate(
+            Connector::And,
+            false,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
         self
     }
 
-    fn OR_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("OR NOT ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
+    fn DO_UPDATE_WHERE_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>,
+    {
+        self.condition_target = ConditionTarget::ConflictUpdate;
+        self.push_cond(Connector::And, condition.into());
         self
     }
 
-    fn OR(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("OR ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
+    fn EXCEPT(&mut self, query: &Self) -> &mut Self {
+        self.push_set_operation(SetOperator::Except, query);
         self
     }
 
-    fn ORDER_BY(&mut self, columns: &[&str]) -> &mut Self {
-        let columns = columns
-            .iter()
-            .map(|column| sql_injection_prevention(&column.to_string()))
-            .collect::<Vec<String>>();
-        self.query.push_str("ORDER BY ");
-        self.query.push_str(&columns.join(", "));
-        self.query.push_str(" ");
+    fn FROM<T>(&mut self, table: T) -> &mut Self
+    where
+        T: Into<TableRef>,
+    {
+        self.statement.from.push(table.into());
         self
     }
 
-    fn RETURNING(&mut self, columns: &[&str]) -> &mut Self {
-        let columns = columns
-            .iter()
-            .map(|column| sql_injection_prevention(&column.to_string()))
-            .collect::<Vec<String>>();
-        self.query.push_str("RETURNING ");
-        self.query.push_str(&columns.join(", "));
-        self.query.push_str(" ");
+    fn FULL_JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>,
+    {
+        self.push_join(
+            JoinKind::Full,
+            table.into(),
+            vec![(column1.into(), operator, column2.into())],
+        );
         self
     }
 
-    fn SELECT_DISTINCT(&mut self, columns: &[&str]) -> &mut Self {
-        let columns = columns
-            .iter()
-            .map(|column| sql_injection_prevention(&column.to_string()))
-            .collect::<Vec<String>>();
-        self.query.push_str("SELECT DISTINCT ");
-        self.query.push_str(&columns.join(", "));
-        self.query.push_str(" ");
+    fn GROUP_BY<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        let columns = columns.iter().map(|column| column.clone().into());
+        self.statement.group_by.extend(columns);
         self
     }
 
-    fn SELECT(&mut self, columns: &[&str]) -> &mut Self {
-        let columns = columns
-            .iter()
-            .map(|column| sql_injection_prevention(&column.to_string()))
-            .collect::<Vec<String>>();
-        self.query.push_str("SELECT ");
-        self.query.push_str(&columns.join(", "));
-        self.query.push_str(" ");
+    fn HAVING_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>,
+    {
+        self.condition_target = ConditionTarget::Having;
+        self.push_cond(Connector::And, condition.into());
         self
     }
 
-    fn SET(&mut self, columns: &[&str]) -> &mut Self {
-        let columns = columns
+    fn HAVING<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.condition_target = ConditionTarget::Having;
+        self.push_predicate(
+            Connector::And,
+            false,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
+        self
+    }
+
+    fn INSERT_AS_SLICE<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Into<Value> + Clone,
+    {
+        self.statement.kind = Some(StatementKind::Insert);
+        self.statement.columns = columns_and_values
+            .iter()
+            .map(|(column, _)| column.to_string())
+            .collect();
+        self.statement.source = None;
+        self.statement.rows = vec![columns_and_values
             .iter()
-            .map(|column| sql_injection_prevention(&column.to_string()))
-            .collect::<Vec<String>>();
-        self.query.push_str("SET ");
-        self.query.push_str(&columns.join(", "));
-        self.query.push_str(" ");
+            .map(|(_, value)| Expr::Value(value.clone().into()))
+            .collect()];
         self
     }
 
-    fn UPDATE_AS_SLICE(&mut self, columns_and_values: &[(&str, &str)]) -> &mut Self {
-        let mut sets = String::new();
-        for (column, value) in columns_and_values {
-            sets.push_str(&sql_injection_prevention(&column.to_string()));
-            sets.push_str(" = ");
-            sets.push_str("'");
-            sets.push_str(&sql_injection_prevention(&value.to_string()));
-            sets.push_str("', ");
-        }
-        if sets.len() <= 2 {
-            panic!("No columns and values provided");
-        }
-        // let table = sql_injection_prevention(&table.to_string());
-        self.query.push_str("UPDATE ");
-        self.query.push_str(&self.table);
-        self.query.push_str(" SET ");
-        self.query.push_str(&sets[..sets.len() - 2]);
-        self.query.push_str(" ");
+    fn INSERT_FROM_SELECT(&mut self, columns: &[&str], query: &Self) -> &mut Self {
+        self.statement.kind = Some(StatementKind::Insert);
+        self.statement.columns = columns.iter().map(|column| column.to_string()).collect();
+        self.statement.rows.clear();
+        self.statement.source = Some(query.into());
         self
     }
 
-    fn UPDATE<T>(&mut self, columns: &T) -> &mut Self
+    fn INSERT<T>(&mut self, columns: &T) -> &mut Self
     where
-        T: Iterable,
+        T: ToColumns,
     {
-        let iterable: HashMap<String, String> = iterate_struct(columns);
+        let iterable = self.read_columns(columns.insert_columns());
+        let columns_and_values = iterable
+            .iter()
+            .map(|(column, value)| (column.as_str(), value.clone()))
+            .collect::<Vec<(&str, Value)>>();
+        self.INSERT_AS_SLICE(&columns_and_values)
+    }
 
-        let mut values: Vec<(&str, &str)> = Vec::new();
-        for item in &iterable {
-            if !(item.1 == "" || item.1 == "null") {
-                values.push((&item.0, &item.1));
+    fn INSERT_MANY<T>(&mut self, rows: &[T]) -> &mut Self
+    where
+        T: ToColumns,
+    {
+        let rows = rows
+            .iter()
+            .map(|row| self.read_columns(row.insert_columns()))
+            .collect::<Vec<_>>();
+        let mut columns: Vec<String> = Vec::new();
+        for row in &rows {
+            for (column, _) in row {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
             }
         }
+        self.statement.kind = Some(StatementKind::Insert);
+        self.statement.source = None;
+        self.statement.rows = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| match row.iter().find(|(name, _)| name == column) {
+                        Some((_, value)) => Expr::Value(value.clone()),
+                        None => Expr::Default,
+                    })
+                    .collect()
+            })
+            .collect();
+        self.statement.columns = columns;
+        self
+    }
 
-        let mut sets = String::new();
-        for (column, value) in values {
-            sets.push_str(&sql_injection_prevention(&column.to_string()));
-            sets.push_str(" = ");
-            sets.push_str("'");
-            sets.push_str(&sql_injection_prevention(&value.to_string()));
-            sets.push_str("', ");
-        }
-        if sets.len() <= 2 {
-            panic!("No columns and values provided");
-        }
-        // let table = sql_injection_prevention(&table.to_string());
-        self.query.push_str("UPDATE ");
-        self.query.push_str(&self.table);
-        self.query.push_str(" SET ");
-        self.query.push_str(&sets[..sets.len() - 2]);
-        self.query.push_str(" ");
+    fn INTERSECT(&mut self, query: &Self) -> &mut Self {
+        self.push_set_operation(SetOperator::Intersect, query);
         self
     }
 
-    fn WHERE_AND<T>(&mut self, columns: &T) -> &mut Self
+    fn JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
     where
-        T: Iterable,
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>,
     {
-        let iterable: HashMap<String, String> = iterate_struct(columns);
-
-        if iterable.len() == 0 {
-            return self;
-        }
-
-        let mut columns = String::new();
+        self.push_join(
+            JoinKind::Inner,
+            table.into(),
+            vec![(column1.into(), operator, column2.into())],
+        );
+        self
+    }
 
-        for (column, value) in &iterable {
-            columns.push_str(&sql_injection_prevention(&column.to_string()));
-            columns.push_str(" = '");
-            columns.push_str(&sql_injection_prevention(&value.to_string()));
-            columns.push_str("' AND ");
-        }
+    fn JOIN_COND<T, C>(&mut self, kind: JoinKind, table: T, on: C) -> &mut Self
+    where
+        T: Into<TableRef>,
+        C: Into<Cond>,
+    {
+        self.statement.joins.push(Join {
+            kind,
+            table: table.into(),
+            constraint: JoinConstraint::On(vec![on.into().0]),
+        });
+        self
+    }
 
-        if columns.len() <= 5 {
-            panic!("No columns and values provided");
-        }
+    fn JOIN_ON<T>(&mut self, kind: JoinKind, table: T, on: &[(&str, &str, &str)]) -> &mut Self
+    where
+        T: Into<TableRef>,
+    {
+        let on = on
+            .iter()
+            .map(|(column1, operator, column2)| {
+                (Expr::from(*column1), *operator, Expr::from(*column2))
+            })
+            .collect();
+        self.push_join(kind, table.into(), on);
+        self
+    }
 
-        self.query.push_str("WHERE ");
-        self.query.push_str(&columns[..columns.len() - 5]);
-        self.query.push_str(" ");
+    fn JOIN_USING<T>(&mut self, kind: JoinKind, table: T, columns: &[&str]) -> &mut Self
+    where
+        T: Into<TableRef>,
+    {
+        self.statement.joins.push(Join {
+            kind,
+            table: table.into(),
+            constraint: JoinConstraint::Using(columns.iter().map(|c| c.to_string()).collect()),
+        });
         self
     }
 
-    fn WHERE_NOT(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("WHERE NOT ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
+    fn LEFT_JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>,
+    {
+        self.push_join(
+            JoinKind::Left,
+            table.into(),
+            vec![(column1.into(), operator, column2.into())],
+        );
         self
     }
 
-    fn WHERE(&mut self, operand: &str, operator: &str, result: &str) -> &mut Self {
-        let operand = sql_injection_prevention(&operand.to_string());
-        let operator = sql_injection_prevention(&operator.to_string());
-        let result = sql_injection_prevention(&result.to_string());
-        let predicate = format!("{} {} '{}'", operand, operator, result);
-        self.query.push_str("WHERE ");
-        self.query.push_str(&predicate);
-        self.query.push_str(" ");
+    fn LIMIT(&mut self, limit: u32) -> &mut Self {
+        self.statement.limit = Some(limit);
         self
     }
 
-    fn to_string(&self) -> io::Result<String> {
-        if self.query.is_empty() {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "No query has been built",
-            ))
-        } else {
-            Ok(self.query.clone().trim().to_owned() + ";")
-        }
+    fn OFFSET(&mut self, limit: u32) -> &mut Self {
+        self.statement.offset = Some(limit);
+        self
     }
 
-    fn build(&self) -> io::Result<String> {
-        if self.query.is_empty() {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "No query has been built",
-            ))
-        } else {
-            Ok(self.query.clone().trim().to_owned() + ";")
-        }
+    fn ON_CONFLICT(&mut self, columns: &[&str]) -> &mut Self {
+        self.on_conflict().target =
+            ConflictTarget::Columns(columns.iter().map(|column| column.to_string()).collect());
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn ON_CONFLICT_ON_CONSTRAINT(&mut self, constraint: &str) -> &mut Self {
+        self.on_conflict().target = ConflictTarget::Constraint(constraint.to_string());
+        self
+    }
 
-    #[test]
-    fn should_insert_and_return() {
-        let query = SqlQueryBuilder::table("users")
-            .INSERT_AS_SLICE(&[("id", "1"), ("name", "John")])
-            .RETURNING(&["*"])
-            .build()
-            .unwrap();
+    fn OR_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>,
+    {
+        self.push_cond(Connector::Or, condition.into());
+        self
+    }
 
-        assert_eq!(
-            query,
-            "INSERT INTO users (id, name) VALUES ('1', 'John') RETURNING *;"
+    fn OR_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.push_predicate(
+            Connector::Or,
+            true,
+            operand.into(),
+            operator,
+            result.into_expr(),
         );
+        self
     }
 
-    #[test]
-    fn should_insert_and_return_from_struct() {
+    fn OR<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.push_predicate(
+            Connector::Or,
+            false,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
+        self
+    }
 
-        #[derive(Iterable)]
-        struct User {
-            id: Option<i32>,
-            name: Option<String>,
-        }
+    fn ORDER_BY<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        let columns = columns.iter().map(|column| match column.clone().into() {
+            Expr::Column(column) => match column.trim().rsplit_once(' ') {
+                Some((column, direction))
+                    if direction.eq_ignore_ascii_case("ASC")
+                        || direction.eq_ignore_ascii_case("DESC") =>
+                {
+                    OrderBy {
+                        expr: Expr::Column(column.trim().to_string()),
+                        direction: Some(direction.to_uppercase()),
+                    }
+                }
+                _ => OrderBy {
+                    expr: Expr::Column(column),
+                    direction: None,
+                },
+            },
+            expr => OrderBy {
+                expr,
+                direction: None,
+            },
+        });
+        self.statement.order_by.extend(columns);
+        self
+    }
+
+    fn RETURNING<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        let columns = columns.iter().map(|column| column.clone().into());
+        self.statement.returning.extend(columns);
+        self
+    }
+
+    fn RIGHT_JOIN<T, L, R>(&mut self, table: T, column1: L, operator: &str, column2: R) -> &mut Self
+    where
+        T: Into<TableRef>,
+        L: Into<Expr>,
+        R: Into<Expr>,
+    {
+        self.push_join(
+            JoinKind::Right,
+            table.into(),
+            vec![(column1.into(), operator, column2.into())],
+        );
+        self
+    }
+
+    fn SELECT_DISTINCT<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        self.SELECT(columns);
+        self.statement.distinct = true;
+        self
+    }
+
+    fn SELECT<C>(&mut self, columns: &[C]) -> &mut Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        let columns = columns.iter().map(|column| column.clone().into());
+        self.statement.kind = Some(StatementKind::Select);
+        self.statement.projection.extend(columns);
+        self
+    }
+
+    fn SET(&mut self, columns: &[&str]) -> &mut Self {
+        let columns = columns
+            .iter()
+            .map(|column| Assignment::Raw(sql_injection_prevention(column)));
+        self.statement.kind = Some(StatementKind::Update);
+        self.statement.assignments.extend(columns);
+        self
+    }
+
+    fn UNION(&mut self, query: &Self) -> &mut Self {
+        self.push_set_operation(SetOperator::Union, query);
+        self
+    }
+
+    fn UNION_ALL(&mut self, query: &Self) -> &mut Self {
+        self.push_set_operation(SetOperator::UnionAll, query);
+        self
+    }
+
+    fn UPDATE_AS_SLICE<V>(&mut self, columns_and_values: &[(&str, V)]) -> &mut Self
+    where
+        V: Operand + Clone,
+    {
+        if columns_and_values.is_empty() {
+            panic!("No columns and values provided");
+        }
+        let assignments = columns_and_values
+            .iter()
+            .map(|(column, value)| Assignment::Set(column.to_string(), value.clone().into_expr()));
+        self.statement.kind = Some(StatementKind::Update);
+        self.statement.assignments.extend(assignments);
+        self
+    }
+
+    fn UPDATE<T>(&mut self, columns: &T) -> &mut Self
+    where
+        T: ToColumns,
+    {
+        let iterable = self.read_columns(columns.update_columns());
+
+        let mut values: Vec<(&str, Value)> = Vec::new();
+        for item in &iterable {
+            if !item.1.is_null() {
+                values.push((item.0.as_str(), item.1.clone()));
+            }
+        }
+
+        self.UPDATE_AS_SLICE(&values)
+    }
+
+    fn USING<T>(&mut self, tables: &[T]) -> &mut Self
+    where
+        T: Into<TableRef> + Clone,
+    {
+        self.statement
+            .using
+            .extend(tables.iter().map(|table| table.clone().into()));
+        self
+    }
+
+    fn WHERE_AND<T>(&mut self, columns: &T) -> &mut Self
+    where
+        T: ToColumns,
+    {
+        let iterable = self.read_columns(columns.to_columns());
+
+        self.condition_target = ConditionTarget::Where;
+        for (column, value) in iterable {
+            if !value.is_null() {
+                self.push_predicate(
+                    Connector::And,
+                    false,
+                    column.into(),
+                    "=",
+                    Expr::Value(value),
+                );
+            }
+        }
+        self
+    }
+
+    fn WHERE_COND<C>(&mut self, condition: C) -> &mut Self
+    where
+        C: Into<Cond>,
+    {
+        self.condition_target = ConditionTarget::Where;
+        self.push_cond(Connector::And, condition.into());
+        self
+    }
+
+    fn WHERE_BETWEEN<E, V>(&mut self, operand: E, low: V, high: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.WHERE_COND(Cond::between(operand, low, high))
+    }
+
+    fn WHERE_EXISTS(&mut self, subquery: &Self) -> &mut Self {
+        self.condition_target = ConditionTarget::Where;
+        self.push_condition(ConditionItem {
+            connector: Connector::And,
+            negated: false,
+            predicate: Predicate::Exists(subquery.into()),
+        });
+        self
+    }
+
+    fn WHERE_IN<E>(&mut self, operand: E, subquery: &Self) -> &mut Self
+    where
+        E: Into<Expr>,
+    {
+        self.condition_target = ConditionTarget::Where;
+        self.push_condition(ConditionItem {
+            connector: Connector::And,
+            negated: false,
+            predicate: Predicate::In {
+                left: operand.into(),
+                negated: false,
+                subquery: subquery.into(),
+            },
+        });
+        self
+    }
+
+    fn WHERE_IN_LIST<E, V>(&mut self, operand: E, values: &[V]) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone,
+    {
+        self.WHERE_COND(Cond::in_list(operand, values))
+    }
+
+    fn WHERE_LIKE<E, V>(&mut self, operand: E, pattern: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.WHERE_COND(Cond::like(operand, pattern))
+    }
+
+    fn WHERE_NOT_EXISTS(&mut self, subquery: &Self) -> &mut Self {
+        self.condition_target = ConditionTarget::Where;
+        self.push_condition(ConditionItem {
+            connector: Connector::And,
+            negated: true,
+            predicate: Predicate::Exists(subquery.into()),
+        });
+        self
+    }
+
+    fn WHERE_NOT_IN<E>(&mut self, operand: E, subquery: &Self) -> &mut Self
+    where
+        E: Into<Expr>,
+    {
+        self.condition_target = ConditionTarget::Where;
+        self.push_condition(ConditionItem {
+            connector: Connector::And,
+            negated: false,
+            predicate: Predicate::In {
+                left: operand.into(),
+                negated: true,
+                subquery: subquery.into(),
+            },
+        });
+        self
+    }
+
+    fn WHERE_NOT_IN_LIST<E, V>(&mut self, operand: E, values: &[V]) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Into<Value> + Clone,
+    {
+        self.WHERE_COND(Cond::not_in_list(operand, values))
+    }
+
+    fn WHERE_NOT_NULL<E>(&mut self, operand: E) -> &mut Self
+    where
+        E: Into<Expr>,
+    {
+        self.WHERE_COND(Cond::is_not_null(operand))
+    }
+
+    fn WHERE_NULL<E>(&mut self, operand: E) -> &mut Self
+    where
+        E: Into<Expr>,
+    {
+        self.WHERE_COND(Cond::is_null(operand))
+    }
+
+    fn WHERE_NOT<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.condition_target = ConditionTarget::Where;
+        self.push_predicate(
+            Connector::And,
+            true,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
+        self
+    }
+
+    fn WHERE<E, V>(&mut self, operand: E, operator: &str, result: V) -> &mut Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.condition_target = ConditionTarget::Where;
+        self.push_predicate(
+            Connector::And,
+            false,
+            operand.into(),
+            operator,
+            result.into_expr(),
+        );
+        self
+    }
+
+    fn WITH<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>,
+    {
+        self.push_cte(name.into(), None, query);
+        self
+    }
+
+    fn WITH_MATERIALIZED<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>,
+    {
+        self.push_cte(name.into(), Some(true), query);
+        self
+    }
+
+    fn WITH_NOT_MATERIALIZED<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>,
+    {
+        self.push_cte(name.into(), Some(false), query);
+        self
+    }
+
+    fn WITH_RECURSIVE<N>(&mut self, name: N, query: &Self) -> &mut Self
+    where
+        N: Into<CteName>,
+    {
+        self.statement.recursive = true;
+        self.push_cte(name.into(), None, query);
+        self
+    }
+
+    fn to_string(&self) -> io::Result<String> {
+        self.render(None)
+    }
+
+    fn build(&self) -> io::Result<String> {
+        self.render(None)
+    }
+
+    fn build_with_params(&self) -> io::Result<(String, Vec<Value>)> {
+        let mut params = Vec::new();
+        let query = self.render(Some(&mut params))?;
+        Ok((query, params))
+    }
+
+    fn build_batches(&self) -> io::Result<Vec<(String, Vec<Value>)>> {
+        let rows = &self.statement.rows;
+        if self.statement.kind != Some(StatementKind::Insert) || rows.len() <= 1 {
+            return Ok(vec![self.build_with_params()?]);
+        }
+        // Values bound outside the rows, e.g. by an upsert clause.
+        let mut first = self.clone();
+        first.statement.rows.truncate(1);
+        let (_, params) = first.build_with_params()?;
+        let overhead = params.len() - bound_values(&rows[0]);
+
+        let max_params = self.dialect.max_bind_params();
+        let max_rows = self.dialect.max_insert_rows();
+        let mut batches = Vec::new();
+        let mut batch = self.clone();
+        batch.statement.rows.clear();
+        let mut bound = overhead;
+        for row in rows {
+            let row_bound = bound_values(row);
+            let full = bound + row_bound > max_params
+                || max_rows.is_some_and(|max_rows| batch.statement.rows.len() >= max_rows);
+            if full && !batch.statement.rows.is_empty() {
+                batches.push(batch.build_with_params()?);
+                batch.statement.rows.clear();
+                bound = overhead;
+            }
+            batch.statement.rows.push(row.clone());
+            bound += row_bound;
+        }
+        batches.push(batch.build_with_params()?);
+        Ok(batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_insert_and_return() {
+        let query = SqlQueryBuilder::table("users")
+            .INSERT_AS_SLICE(&[("id", "1"), ("name", "John")])
+            .RETURNING(&["*"])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "INSERT INTO users (id, name) VALUES ('1', 'John') RETURNING *;"
+        );
+    }
+
+    #[test]
+    fn should_insert_and_return_from_struct() {
+        #[derive(Iterable)]
+        struct User {
+            id: Option<i32>,
+            name: Option<String>,
+        }
 
         let user = User {
             id: Some(1),
@@ -479,7 +1325,7 @@ mod tests {
 
         assert_eq!(
             query,
-            "INSERT INTO users (id, name) VALUES ('1', 'John') RETURNING *;"
+            "INSERT INTO users (id, name) VALUES (1, 'John') RETURNING *;"
         );
     }
 
@@ -543,7 +1389,7 @@ mod tests {
 
         let user = User {
             user_name: Some("John".to_string()),
-            id: Some("1".to_string())
+            id: Some("1".to_string()),
         };
 
         let query = SqlQueryBuilder::new()
@@ -554,4 +1400,1660 @@ mod tests {
 
         assert_eq!(query, "SELECT * WHERE user_name = 'John' AND id = '1';");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn should_build_with_params() {
+        let (query, params) = SqlQueryBuilder::new()
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .WHERE("id", "=", "1")
+            .AND("name", "=", "O'Brien")
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT id, name FROM users WHERE id = $1 AND name = $2;"
+        );
+        assert_eq!(params, vec![Value::from("1"), Value::from("O'Brien")]);
+    }
+
+    #[test]
+    fn should_insert_and_update_with_params() {
+        let (query, params) = SqlQueryBuilder::table("users")
+            .INSERT_AS_SLICE(&[("id", "1"), ("name", "John")])
+            .RETURNING(&["*"])
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "INSERT INTO users (id, name) VALUES ($1, $2) RETURNING *;"
+        );
+        assert_eq!(params, vec![Value::from("1"), Value::from("John")]);
+
+        let (query, params) = SqlQueryBuilder::table("users")
+            .UPDATE_AS_SLICE(&[("name", "John")])
+            .WHERE("id", "=", "1")
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(query, "UPDATE users SET name = $1 WHERE id = $2;");
+        assert_eq!(params, vec![Value::from("John"), Value::from("1")]);
+    }
+
+    #[test]
+    fn should_render_values_according_to_their_type() {
+        let query = SqlQueryBuilder::table("users")
+            .UPDATE_AS_SLICE(&[
+                ("age", Value::from(30)),
+                ("active", Value::from(true)),
+                ("score", Value::from(9.5)),
+                ("nickname", Value::Null),
+            ])
+            .WHERE("id", "=", 1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "UPDATE users SET age = 30, active = TRUE, score = 9.5, nickname = NULL WHERE id = 1;"
+        );
+    }
+
+    #[test]
+    fn should_insert_null_for_none_fields() {
+        #[derive(Iterable)]
+        struct User {
+            nickname: Option<String>,
+        }
+
+        let user = User { nickname: None };
+
+        let query = SqlQueryBuilder::table("users")
+            .INSERT(&user)
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "INSERT INTO users (nickname) VALUES (NULL);");
+    }
+
+    #[test]
+    fn should_render_per_dialect() {
+        let query = SqlQueryBuilder::<Postgres>::new()
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .WHERE("name", "=", "O'Brien")
+            .ORDER_BY(&["name DESC"])
+            .LIMIT(10)
+            .OFFSET(20)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"SELECT "id", "name" FROM "users" WHERE "name" = 'O''Brien' ORDER BY "name" DESC LIMIT 10 OFFSET 20;"#
+        );
+
+        let (query, _) = SqlQueryBuilder::<MySql>::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .WHERE("id", "=", 1)
+            .OFFSET(5)
+            .build_with_params()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT `id` FROM `users` WHERE `id` = ? LIMIT 18446744073709551615 OFFSET 5;"
+        );
+
+        let query = SqlQueryBuilder::<Sqlite>::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .WHERE("active", "=", true)
+            .build()
+            .unwrap();
+        assert_eq!(query, r#"SELECT * FROM "users" WHERE "active" = 1;"#);
+
+        let (query, _) = SqlQueryBuilder::<SqlServer>::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .WHERE("id", "=", 1)
+            .ORDER_BY(&["id"])
+            .LIMIT(10)
+            .build_with_params()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT [id] FROM [users] WHERE [id] = @p1 ORDER BY [id] OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY;"
+        );
+    }
+
+    #[test]
+    fn should_fail_on_clause_unsupported_by_dialect() {
+        let result = SqlQueryBuilder::<MySql>::table("users")
+            .INSERT_AS_SLICE(&[("name", "John")])
+            .RETURNING(&["id"])
+            .build();
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "RETURNING is not supported by the MySQL dialect"
+        );
+    }
+
+    #[test]
+    fn should_build_clauses_regardless_of_call_order() {
+        let query = SqlQueryBuilder::new()
+            .WHERE("active", "=", true)
+            .LIMIT(10)
+            .ORDER_BY(&["name"])
+            .FROM("users")
+            .SELECT(&["id", "name"])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT id, name FROM users WHERE active = TRUE ORDER BY name LIMIT 10;"
+        );
+    }
+
+    #[test]
+    fn should_select_from_builder_table() {
+        let query = SqlQueryBuilder::<SqlServer>::table("users")
+            .SELECT(&["id"])
+            .LIMIT(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT [id] FROM [users] ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 5 ROWS ONLY;"
+        );
+    }
+
+    #[test]
+    fn should_delete_where_id_equal_1_and_return() {
+        let query = SqlQueryBuilder::table("users")
+            .DELETE()
+            .WHERE("id", "=", 1)
+            .RETURNING(&["id"])
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "DELETE FROM users WHERE id = 1 RETURNING id;");
+    }
+
+    #[test]
+    fn should_delete_by_non_null_fields_of_struct() {
+        #[derive(Iterable)]
+        struct User {
+            id: Option<i32>,
+            name: Option<String>,
+        }
+
+        let user = User {
+            id: Some(1),
+            name: None,
+        };
+
+        let query = SqlQueryBuilder::new()
+            .DELETE_BY(&user)
+            .FROM("users")
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "DELETE FROM users WHERE id = 1;");
+    }
+
+    #[test]
+    fn should_group_by_with_having_and_aggregates() {
+        let query = SqlQueryBuilder::new()
+            .SELECT(&[Expr::from("country"), COUNT("*").AS("total"), AVG("age")])
+            .FROM("users")
+            .WHERE("active", "=", true)
+            .GROUP_BY(&["country"])
+            .HAVING(COUNT("*"), ">", 10)
+            .OR(SUM("age").DISTINCT(), "<", 100)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT country, COUNT(*) AS total, AVG(age) FROM users WHERE active = TRUE GROUP BY country HAVING COUNT(*) > 10 OR SUM(DISTINCT age) < 100;"
+        );
+    }
+
+    #[test]
+    fn should_build_every_kind_of_join() {
+        let query = SqlQueryBuilder::new()
+            .SELECT(&["u.name", "o.total", "p.title"])
+            .FROM(("users", "u"))
+            .LEFT_JOIN(("orders", "o"), "o.user_id", "=", "u.id")
+            .JOIN_ON(
+                JoinKind::Right,
+                ("products", "p"),
+                &[("p.id", "=", "o.product_id"), ("p.price", "<=", "o.total")],
+            )
+            .JOIN_USING(JoinKind::Full, "profiles", &["user_id"])
+            .CROSS_JOIN("currencies")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT u.name, o.total, p.title FROM users AS u LEFT JOIN orders AS o ON o.user_id = u.id RIGHT JOIN products AS p ON p.id = o.product_id AND p.price <= o.total FULL JOIN profiles USING (user_id) CROSS JOIN currencies;"
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_join_operator_and_unsupported_join() {
+        let result = SqlQueryBuilder::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .JOIN(
+                "orders",
+                "orders.user_id",
+                "= 1; DROP TABLE users",
+                "users.id",
+            )
+            .build();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid join operator: = 1; DROP TABLE users"
+        );
+
+        let result = SqlQueryBuilder::<MySql>::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .FULL_JOIN("orders", "orders.user_id", "=", "users.id")
+            .build();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "FULL JOIN is not supported by the MySQL dialect"
+        );
+    }
+
+    #[test]
+    fn should_embed_subqueries_and_merge_their_params() {
+        let mut orders = SqlQueryBuilder::new();
+        orders
+            .SELECT(&["user_id"])
+            .FROM("orders")
+            .WHERE("total", ">", 100);
+
+        let mut totals = SqlQueryBuilder::new();
+        totals
+            .SELECT(&[Expr::from("user_id"), SUM("total").AS("spent")])
+            .FROM("orders")
+            .GROUP_BY(&["user_id"]);
+
+        let mut last_login = SqlQueryBuilder::new();
+        last_login
+            .SELECT(&[MAX("created_at")])
+            .FROM("logins")
+            .WHERE("source", "=", "web");
+
+        let (query, params) = SqlQueryBuilder::new()
+            .SELECT(&[
+                Expr::from("u.name"),
+                Expr::from("t.spent"),
+                Expr::from(&last_login).AS("last_login"),
+            ])
+            .FROM(("users", "u"))
+            .JOIN((&totals, "t"), "t.user_id", "=", "u.id")
+            .WHERE("u.active", "=", true)
+            .WHERE_IN("u.id", &orders)
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT u.name, t.spent, (SELECT MAX(created_at) FROM logins WHERE source = $1) AS last_login FROM users AS u JOIN (SELECT user_id, SUM(total) AS spent FROM orders GROUP BY user_id) AS t ON t.user_id = u.id WHERE u.active = $2 AND u.id IN (SELECT user_id FROM orders WHERE total > $3);"
+        );
+        assert_eq!(
+            params,
+            vec![Value::from("web"), Value::from(true), Value::from(100)]
+        );
+    }
+
+    #[test]
+    fn should_select_where_not_exists() {
+        let mut orders = SqlQueryBuilder::new();
+        orders
+            .SELECT(&["1"])
+            .FROM("orders")
+            .JOIN("users", "orders.user_id", "=", "users.id");
+
+        let query = SqlQueryBuilder::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .WHERE_NOT_EXISTS(&orders)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT id FROM users WHERE NOT EXISTS (SELECT 1 FROM orders JOIN users ON orders.user_id = users.id);"
+        );
+    }
+
+    #[test]
+    fn should_propagate_subquery_errors() {
+        let subquery = SqlQueryBuilder::new();
+
+        let result = SqlQueryBuilder::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .WHERE_EXISTS(&subquery)
+            .build();
+
+        assert_eq!(result.unwrap_err().to_string(), "No query has been built");
+    }
+
+    #[test]
+    fn should_select_from_common_table_expressions() {
+        let mut active_users = SqlQueryBuilder::<Postgres>::new();
+        active_users
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .WHERE("active", "=", true);
+
+        let mut big_orders = SqlQueryBuilder::<Postgres>::new();
+        big_orders
+            .SELECT(&["user_id", "total"])
+            .FROM("orders")
+            .WHERE("total", ">", 100);
+
+        let (query, params) = SqlQueryBuilder::<Postgres>::new()
+            .WITH("active_users", &active_users)
+            .WITH_MATERIALIZED(("big_orders", &["user_id", "total"]), &big_orders)
+            .SELECT(&["u.name", "o.total"])
+            .FROM(("active_users", "u"))
+            .JOIN(("big_orders", "o"), "o.user_id", "=", "u.id")
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"WITH "active_users" AS (SELECT "id", "name" FROM "users" WHERE "active" = $1), "big_orders" ("user_id", "total") AS MATERIALIZED (SELECT "user_id", "total" FROM "orders" WHERE "total" > $2) SELECT "u"."name", "o"."total" FROM "active_users" AS "u" JOIN "big_orders" AS "o" ON "o"."user_id" = "u"."id";"#
+        );
+        assert_eq!(params, vec![Value::from(true), Value::from(100)]);
+
+        let result = SqlQueryBuilder::<MySql>::new()
+            .WITH_MATERIALIZED(
+                "users_cte",
+                &SqlQueryBuilder::<MySql>::table("users")
+                    .SELECT(&["*"])
+                    .clone(),
+            )
+            .SELECT(&["*"])
+            .FROM("users_cte")
+            .build();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "MATERIALIZED is not supported by the MySQL dialect"
+        );
+    }
+
+    #[test]
+    fn should_build_with_recursive() {
+        let mut categories = SqlQueryBuilder::new();
+        categories
+            .SELECT(&["id", "parent_id"])
+            .FROM("categories")
+            .WHERE("id", "=", 1);
+
+        let query = SqlQueryBuilder::new()
+            .WITH_RECURSIVE(("tree", &["id", "parent_id"]), &categories)
+            .SELECT(&["id"])
+            .FROM("tree")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "WITH RECURSIVE tree (id, parent_id) AS (SELECT id, parent_id FROM categories WHERE id = 1) SELECT id FROM tree;"
+        );
+    }
+
+    #[test]
+    fn should_combine_queries_with_set_operations() {
+        let mut archived = SqlQueryBuilder::new();
+        archived
+            .SELECT(&["id", "name"])
+            .FROM("archived_users")
+            .WHERE("deleted", "=", false);
+
+        let mut banned = SqlQueryBuilder::new();
+        banned
+            .SELECT(&["id", "name"])
+            .FROM("banned_users")
+            .ORDER_BY(&["id DESC"])
+            .LIMIT(10);
+
+        let (query, params) = SqlQueryBuilder::new()
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .WHERE("active", "=", true)
+            .UNION_ALL(&archived)
+            .EXCEPT(&banned)
+            .ORDER_BY(&["name"])
+            .LIMIT(5)
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT id, name FROM users WHERE active = $1 UNION ALL SELECT id, name FROM archived_users WHERE deleted = $2 EXCEPT (SELECT id, name FROM banned_users ORDER BY id DESC LIMIT 10) ORDER BY name LIMIT 5;"
+        );
+        assert_eq!(params, vec![Value::from(true), Value::from(false)]);
+
+        let query = SqlQueryBuilder::<Sqlite>::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .INTERSECT(
+                SqlQueryBuilder::<Sqlite>::table("admins")
+                    .SELECT(&["id"])
+                    .LIMIT(3),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"SELECT "id" FROM "users" INTERSECT SELECT * FROM (SELECT "id" FROM "admins" LIMIT 3);"#
+        );
+    }
+
+    #[test]
+    fn should_build_recursive_tree_traversal() {
+        let mut tree = SqlQueryBuilder::<Postgres>::new();
+        tree.SELECT(&["id", "parent_id"])
+            .FROM("categories")
+            .WHERE("id", "=", 1)
+            .UNION_ALL(
+                SqlQueryBuilder::<Postgres>::new()
+                    .SELECT(&["c.id", "c.parent_id"])
+                    .FROM(("categories", "c"))
+                    .JOIN("tree", "c.parent_id", "=", "tree.id"),
+            );
+
+        let query = SqlQueryBuilder::<Postgres>::new()
+            .WITH_RECURSIVE("tree", &tree)
+            .SELECT(&["id"])
+            .FROM("tree")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"WITH RECURSIVE "tree" AS (SELECT "id", "parent_id" FROM "categories" WHERE "id" = 1 UNION ALL SELECT "c"."id", "c"."parent_id" FROM "categories" AS "c" JOIN "tree" ON "c"."parent_id" = "tree"."id") SELECT "id" FROM "tree";"#
+        );
+    }
+
+    #[test]
+    fn should_reject_set_operations_with_different_arity() {
+        let result = SqlQueryBuilder::new()
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .UNION(SqlQueryBuilder::new().SELECT(&["id"]).FROM("admins"))
+            .build();
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "UNION operands have different numbers of columns: 2 and 1"
+        );
+    }
+
+    #[test]
+    fn should_nest_condition_groups() {
+        let (query, params) = SqlQueryBuilder::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .WHERE("active", "=", true)
+            .AND_COND(Cond::any([
+                Cond::new("role", "=", "admin"),
+                Cond::all([
+                    Cond::new("role", "=", "editor"),
+                    Cond::new("verified", "=", true),
+                ]),
+            ]))
+            .OR_COND(not(Cond::any([
+                Cond::new("age", "<", 18),
+                Cond::new("country", "=", "XX"),
+            ])))
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT * FROM users WHERE active = $1 AND (role = $2 OR (role = $3 AND verified = $4)) OR NOT (age < $5 OR country = $6);"
+        );
+        assert_eq!(params.len(), 6);
+
+        let query = SqlQueryBuilder::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .WHERE_COND(Cond::any([Cond::new("a", "=", 1), Cond::new("b", "=", 2)]))
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "SELECT * FROM users WHERE a = 1 OR b = 2;");
+    }
+
+    #[test]
+    fn should_use_condition_groups_in_having_and_join_on() {
+        let query = SqlQueryBuilder::<Postgres>::new()
+            .SELECT(&[Expr::from("u.country"), COUNT("o.id")])
+            .FROM(("users", "u"))
+            .JOIN_COND(
+                JoinKind::Left,
+                ("orders", "o"),
+                Cond::all([
+                    Cond::columns("o.user_id", "=", "u.id"),
+                    Cond::any([
+                        Cond::new("o.status", "=", "paid"),
+                        Cond::new("o.total", ">", 0),
+                    ]),
+                ]),
+            )
+            .GROUP_BY(&["u.country"])
+            .HAVING_COND(not(Cond::new(COUNT("o.id"), "=", 0)))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"SELECT "u"."country", COUNT("o"."id") FROM "users" AS "u" LEFT JOIN "orders" AS "o" ON "o"."user_id" = "u"."id" AND ("o"."status" = 'paid' OR "o"."total" > 0) GROUP BY "u"."country" HAVING NOT COUNT("o"."id") = 0;"#
+        );
+    }
+
+    #[test]
+    fn should_render_rich_predicates() {
+        let (query, params) = SqlQueryBuilder::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .WHERE_IN_LIST("status", &["active", "pending"])
+            .AND_COND(Cond::not_between("age", 18, 65))
+            .AND_COND(Cond::like("name", "50\\%%").escape('\\'))
+            .AND_COND(Cond::is_not_null("email"))
+            .AND("deleted_at", "IS", Value::Null)
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r"SELECT * FROM users WHERE status IN ($1, $2) AND age NOT BETWEEN $3 AND $4 AND name LIKE $5 ESCAPE '\\' AND email IS NOT NULL AND deleted_at IS NULL;"
+        );
+        assert_eq!(params.len(), 5);
+
+        let query = SqlQueryBuilder::new()
+            .SELECT(&["*"])
+            .FROM("users")
+            .WHERE_NOT_IN_LIST("id", &[] as &[i32])
+            .build()
+            .unwrap();
+
+        assert_eq!(query, "SELECT * FROM users WHERE 1 = 1;");
+    }
+
+    #[test]
+    fn should_render_ilike_and_is_distinct_from_per_dialect() {
+        fn query<D: Dialect>() -> String {
+            SqlQueryBuilder::<D>::new()
+                .SELECT(&["id"])
+                .FROM("users")
+                .WHERE_COND(Cond::ilike("name", "jo%"))
+                .AND_COND(Cond::is_distinct_from("manager_id", 1))
+                .build()
+                .unwrap()
+        }
+
+        assert_eq!(
+            query::<Postgres>(),
+            r#"SELECT "id" FROM "users" WHERE "name" ILIKE 'jo%' AND "manager_id" IS DISTINCT FROM 1;"#
+        );
+        assert_eq!(
+            query::<MySql>(),
+            "SELECT `id` FROM `users` WHERE LOWER(`name`) LIKE LOWER('jo%') AND NOT (`manager_id` <=> 1);"
+        );
+        assert_eq!(
+            query::<Sqlite>(),
+            r#"SELECT "id" FROM "users" WHERE LOWER("name") LIKE LOWER('jo%') AND "manager_id" IS NOT 1;"#
+        );
+    }
+
+    #[test]
+    fn should_compare_columns_values_and_expressions() {
+        let (query, params) = SqlQueryBuilder::<Postgres>::new()
+            .SELECT(&["id"])
+            .FROM("posts")
+            .WHERE("updated_at", ">", col("created_at"))
+            .AND("title", "=", "created_at")
+            .AND(val(10), "<", col("views"))
+            .OR(raw("LOWER(author)"), "=", raw("LOWER(editor)"))
+            .AND_COND(Cond::between(
+                "published_at",
+                raw("NOW() - INTERVAL '1 day'"),
+                raw("NOW()"),
+            ))
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"SELECT "id" FROM "posts" WHERE "updated_at" > "created_at" AND "title" = $1 AND $2 < "views" OR LOWER(author) = LOWER(editor) AND "published_at" BETWEEN NOW() - INTERVAL '1 day' AND NOW();"#
+        );
+        assert_eq!(params, vec![Value::from("created_at"), Value::from(10)]);
+    }
+
+    #[test]
+    fn should_upsert_per_dialect() {
+        fn upsert<D: Dialect>() -> io::Result<String> {
+            SqlQueryBuilder::<D>::table("users")
+                .INSERT_AS_SLICE(&[("id", Value::from(1)), ("name", Value::from("John"))])
+                .ON_CONFLICT(&["id"])
+                .DO_UPDATE(&["name"])
+                .build()
+        }
+
+        assert_eq!(
+            upsert::<Postgres>().unwrap(),
+            r#"INSERT INTO "users" ("id", "name") VALUES (1, 'John') ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name";"#
+        );
+        assert_eq!(
+            upsert::<MySql>().unwrap(),
+            "INSERT INTO `users` (`id`, `name`) VALUES (1, 'John') ON DUPLICATE KEY UPDATE `name` = VALUES(`name`);"
+        );
+        assert_eq!(
+            upsert::<SqlServer>().unwrap_err().to_string(),
+            "Upserts are not supported by the SQL Server dialect"
+        );
+
+        let query = SqlQueryBuilder::<MySql>::table("users")
+            .INSERT_AS_SLICE(&[("id", 1)])
+            .DO_NOTHING()
+            .build()
+            .unwrap();
+        assert_eq!(query, "INSERT IGNORE INTO `users` (`id`) VALUES (1);");
+    }
+
+    #[test]
+    fn should_upsert_with_expressions_and_condition() {
+        let (query, params) = SqlQueryBuilder::<Sqlite>::table("counters")
+            .INSERT_AS_SLICE(&[("name", Value::from("visits")), ("hits", Value::from(1))])
+            .ON_CONFLICT(&["name"])
+            .DO_UPDATE_SET(&[
+                ("hits", raw("counters.hits + excluded.hits")),
+                ("locked", val(false)),
+            ])
+            .DO_UPDATE_WHERE("counters.locked", "=", false)
+            .RETURNING(&["hits"])
+            .build_with_params()
+            .unwrap();
+
+        assert_eq!(
+            query,
+            r#"INSERT INTO "counters" ("name", "hits") VALUES (?, ?) ON CONFLICT ("name") DO UPDATE SET "hits" = counters.hits + excluded.hits, "locked" = ? WHERE "counters"."locked" = ? RETURNING "hits";"#
+        );
+        assert_eq!(
+            params,
+            vec![
+                Value::from("visits"),
+                Value::from(1),
+                Value::from(false),
+                Value::from(false)
+            ]
+        );
+
+        let query = SqlQueryBuilder::<Postgres>::table("users")
+            .INSERT_AS_SLICE(&[("email", "john@example.com")])
+            .ON_CONFLICT_ON_CONSTRAINT("users_email_key")
+            .DO_NOTHING()
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "users" ("email") VALUES ('john@example.com') ON CONFLICT ON CONSTRAINT "users_email_key" DO NOTHING;"#
+        );
+    }
+
+    #[test]
+    fn should_insert_many_rows() {
+        #[derive(Iterable)]
+        struct User {
+            name: Option<String>,
+        }
+        let users = [
+            User {
+                name: Some(String::from("John")),
+            },
+            User { name: None },
+        ];
+
+        let query = SqlQueryBuilder::table("users")
+            .INSERT_MANY(&users)
+            .build()
+            .unwrap();
+        assert_eq!(query, "INSERT INTO users (name) VALUES ('John'), (NULL);");
+
+        let (query, params) = SqlQueryBuilder::<Sqlite>::table("users")
+            .INSERT_MANY(&users)
+            .build_with_params()
+            .unwrap();
+        assert_eq!(query, r#"INSERT INTO "users" ("name") VALUES (?), (?);"#);
+        assert_eq!(params, vec![Value::from("John"), Value::Null]);
+    }
+
+    #[test]
+    fn should_split_insert_many_into_batches() {
+        #[derive(Iterable)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        let points = (0..2500)
+            .map(|i| Point { x: i, y: i })
+            .collect::<Vec<Point>>();
+
+        let batches = SqlQueryBuilder::<SqlServer>::table("points")
+            .INSERT_MANY(&points)
+            .build_batches()
+            .unwrap();
+        let sizes = batches
+            .iter()
+            .map(|(_, params)| params.len())
+            .collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![2000, 2000, 1000]);
+
+        let batches = SqlQueryBuilder::<Postgres>::table("points")
+            .INSERT_MANY(
+                &(0..40000)
+                    .map(|i| Point { x: i, y: i })
+                    .collect::<Vec<Point>>(),
+            )
+            .ON_CONFLICT(&["x"])
+            .DO_UPDATE_SET(&[("y", val(0))])
+            .build_batches()
+            .unwrap();
+        let sizes = batches
+            .iter()
+            .map(|(_, params)| params.len())
+            .collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![65535, 14467]);
+        assert!(batches[1]
+            .0
+            .ends_with(r#"($14465, $14466) ON CONFLICT ("x") DO UPDATE SET "y" = $14467;"#));
+    }
+
+    #[test]
+    fn should_insert_from_select() {
+        let mut archived = SqlQueryBuilder::<Postgres>::new();
+        archived
+            .SELECT(&["id", "name"])
+            .FROM("users")
+            .WHERE("deleted", "=", true);
+
+        let (query, params) = SqlQueryBuilder::<Postgres>::table("archived_users")
+            .INSERT_FROM_SELECT(&["id", "name"], &archived)
+            .ON_CONFLICT(&["id"])
+            .DO_NOTHING()
+            .RETURNING(&["id"])
+            .build_with_params()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "archived_users" ("id", "name") SELECT "id", "name" FROM "users" WHERE "deleted" = $1 ON CONFLICT ("id") DO NOTHING RETURNING "id";"#
+        );
+        assert_eq!(params, vec![Value::from(true)]);
+
+        let query = SqlQueryBuilder::<Sqlite>::table("archived_users")
+            .INSERT_FROM_SELECT(
+                &["id"],
+                SqlQueryBuilder::<Sqlite>::table("users").SELECT(&["id"]),
+            )
+            .ON_CONFLICT(&["id"])
+            .DO_UPDATE(&["id"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "archived_users" ("id") SELECT * FROM (SELECT "id" FROM "users") WHERE true ON CONFLICT ("id") DO UPDATE SET "id" = EXCLUDED."id";"#
+        );
+
+        let result = SqlQueryBuilder::table("archived_users")
+            .INSERT_FROM_SELECT(
+                &["id"],
+                SqlQueryBuilder::table("users").SELECT(&["id", "name"]),
+            )
+            .build();
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "INSERT has 1 columns but its SELECT returns 2"
+        );
+    }
+
+    #[test]
+    fn should_update_from_other_tables_per_dialect() {
+        fn update<D: Dialect>() -> io::Result<String> {
+            SqlQueryBuilder::<D>::table("users")
+                .UPDATE_AS_SLICE(&[("total", col("o.total")), ("checked", val(true))])
+                .FROM(("orders", "o"))
+                .WHERE("o.user_id", "=", col("users.id"))
+                .build()
+        }
+
+        assert_eq!(
+            update::<Postgres>().unwrap(),
+            r#"UPDATE "users" SET "total" = "o"."total", "checked" = TRUE FROM "orders" AS "o" WHERE "o"."user_id" = "users"."id";"#
+        );
+        assert_eq!(
+            update::<MySql>().unwrap(),
+            "UPDATE `users`, `orders` AS `o` SET `total` = `o`.`total`, `checked` = TRUE WHERE `o`.`user_id` = `users`.`id`;"
+        );
+
+        let query = SqlQueryBuilder::<MySql>::table("users")
+            .UPDATE_AS_SLICE(&[("total", col("o.total"))])
+            .JOIN(("orders", "o"), "o.user_id", "=", "users.id")
+            .WHERE("o.status", "=", "paid")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "UPDATE `users` JOIN `orders` AS `o` ON `o`.`user_id` = `users`.`id` SET `total` = `o`.`total` WHERE `o`.`status` = 'paid';"
+        );
+    }
+
+    #[test]
+    fn should_delete_using_other_tables_per_dialect() {
+        fn delete<D: Dialect>() -> io::Result<String> {
+            SqlQueryBuilder::<D>::table("sessions")
+                .DELETE()
+                .USING(&[("users", "u")])
+                .WHERE("u.id", "=", col("sessions.user_id"))
+                .AND("u.banned", "=", true)
+                .build()
+        }
+
+        assert_eq!(
+            delete::<Postgres>().unwrap(),
+            r#"DELETE FROM "sessions" USING "users" AS "u" WHERE "u"."id" = "sessions"."user_id" AND "u"."banned" = TRUE;"#
+        );
+        assert_eq!(
+            delete::<MySql>().unwrap(),
+            "DELETE `sessions` FROM `sessions`, `users` AS `u` WHERE `u`.`id` = `sessions`.`user_id` AND `u`.`banned` = TRUE;"
+        );
+        assert_eq!(
+            delete::<SqlServer>().unwrap(),
+            "DELETE FROM [sessions] FROM [users] AS [u] WHERE [u].[id] = [sessions].[user_id] AND [u].[banned] = 1;"
+        );
+        assert_eq!(
+            delete::<Sqlite>().unwrap_err().to_string(),
+            "DELETE with other tables is not supported by the SQLite dialect"
+        );
+    }
+
+    #[test]
+    fn should_keep_field_declaration_order() {
+        #[derive(Iterable)]
+        struct Account {
+            zeta: i32,
+            alpha: Option<String>,
+            mid: bool,
+            beta: i64,
+        }
+        let account = Account {
+            zeta: 1,
+            alpha: Some(String::from("a")),
+            mid: true,
+            beta: 2,
+        };
+
+        for _ in 0..10 {
+            let insert = SqlQueryBuilder::table("accounts")
+                .INSERT(&account)
+                .build()
+                .unwrap();
+            assert_eq!(
+                insert,
+                "INSERT INTO accounts (zeta, alpha, mid, beta) VALUES (1, 'a', TRUE, 2);"
+            );
+
+            let update = SqlQueryBuilder::table("accounts")
+                .UPDATE(&account)
+                .WHERE_AND(&account)
+                .build()
+                .unwrap();
+            assert_eq!(
+                update,
+                "UPDATE accounts SET zeta = 1, alpha = 'a', mid = TRUE, beta = 2 WHERE zeta = 1 AND alpha = 'a' AND mid = TRUE AND beta = 2;"
+            );
+        }
+    }
+
+    #[test]
+    fn should_derive_table() {
+        #[derive(Table)]
+        #[table(name = "users")]
+        struct User {
+            id: i64,
+            name: String,
+            email: Option<String>,
+            avatar: Vec<u8>,
+        }
+        #[derive(Table)]
+        struct BlogPost {
+            id: u32,
+        }
+        let user = User {
+            id: 1,
+            name: String::from("John"),
+            email: None,
+            avatar: vec![0xCA, 0xFE],
+        };
+
+        assert_eq!(User::TABLE_NAME, "users");
+        assert_eq!(User::COLUMNS, ["id", "name", "email", "avatar"]);
+        assert_eq!(BlogPost::TABLE_NAME, "blog_post");
+
+        let query = SqlQueryBuilder::<Postgres>::table_of::<User>()
+            .INSERT(&user)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "users" ("id", "name", "email", "avatar") VALUES (1, 'John', NULL, '\xCAFE'::bytea);"#
+        );
+
+        let query = SqlQueryBuilder::table_of::<User>()
+            .UPDATE(&user)
+            .WHERE_AND(&BlogPost { id: 1 })
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "UPDATE users SET id = 1, name = 'John', avatar = X'CAFE' WHERE id = 1;"
+        );
+    }
+
+    #[test]
+    fn should_honour_column_attributes() {
+        #[derive(Table)]
+        #[table(name = "users", rename_all = "camelCase")]
+        struct User {
+            #[column(primary_key, skip_insert)]
+            user_id: i64,
+            #[column(name = "userName")]
+            name: String,
+            last_login_at: Option<String>,
+            #[column(default)]
+            is_active: Option<bool>,
+            #[column(skip)]
+            #[allow(dead_code)]
+            cache: std::collections::HashMap<String, String>,
+        }
+        let user = User {
+            user_id: 7,
+            name: String::from("John"),
+            last_login_at: None,
+            is_active: None,
+            cache: Default::default(),
+        };
+
+        assert_eq!(
+            User::COLUMNS,
+            ["userId", "userName", "lastLoginAt", "isActive"]
+        );
+        assert_eq!(User::PRIMARY_KEY, ["userId"]);
+
+        let insert = SqlQueryBuilder::table_of::<User>()
+            .INSERT(&user)
+            .build()
+            .unwrap();
+        assert_eq!(
+            insert,
+            "INSERT INTO users (userName, lastLoginAt) VALUES ('John', NULL);"
+        );
+
+        let insert_many = SqlQueryBuilder::table_of::<User>()
+            .INSERT_MANY(&[
+                user,
+                User {
+                    user_id: 8,
+                    name: String::from("Jane"),
+                    last_login_at: None,
+                    is_active: Some(false),
+                    cache: Default::default(),
+                },
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(
+            insert_many,
+            "INSERT INTO users (userName, lastLoginAt, isActive) VALUES ('John', NULL, DEFAULT), ('Jane', NULL, FALSE);"
+        );
+
+        let update = SqlQueryBuilder::table_of::<User>()
+            .UPDATE(&User {
+                user_id: 7,
+                name: String::from("John"),
+                last_login_at: Some(String::from("2024-01-01")),
+                is_active: Some(true),
+                cache: Default::default(),
+            })
+            .WHERE("userId", "=", 7)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            "UPDATE users SET userName = 'John', lastLoginAt = '2024-01-01', isActive = TRUE WHERE userId = 7;"
+        );
+    }
+
+    #[test]
+    fn should_convert_custom_types_with_to_sql_value() {
+        struct Email(String);
+        impl ToSqlValue for Email {
+            fn to_sql_value(&self) -> Value {
+                Value::Text(self.0.to_lowercase())
+            }
+        }
+        #[derive(Table)]
+        #[table(name = "users")]
+        struct User {
+            email: Email,
+            backup_email: Option<Email>,
+        }
+
+        let query = SqlQueryBuilder::table_of::<User>()
+            .INSERT(&User {
+                email: Email(String::from("John@Example.com")),
+                backup_email: None,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO users (email, backup_email) VALUES ('john@example.com', NULL);"
+        );
+    }
+
+    #[cfg(all(
+        feature = "chrono",
+        feature = "uuid",
+        feature = "rust_decimal",
+        feature = "serde_json"
+    ))]
+    #[test]
+    fn should_convert_ecosystem_types() {
+        #[derive(Iterable)]
+        struct Order {
+            id: uuid::Uuid,
+            placed_on: chrono::NaiveDate,
+            total: rust_decimal::Decimal,
+            metadata: Option<serde_json::Value>,
+        }
+        let order = Order {
+            id: uuid::Uuid::nil(),
+            placed_on: chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            total: rust_decimal::Decimal::new(1050, 2),
+            metadata: Some(serde_json::json!({"gift": true})),
+        };
+
+        let query = SqlQueryBuilder::table("orders")
+            .INSERT(&order)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO orders (id, placed_on, total, metadata) VALUES ('00000000-0000-0000-0000-000000000000', '2024-01-31', '10.50', '{\"gift\":true}');"#
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn should_round_trip_dates_and_times() {
+        #[derive(Table, FromRow, Debug, PartialEq)]
+        struct Event {
+            on: chrono::NaiveDate,
+            at: chrono::NaiveTime,
+            starts: chrono::NaiveDateTime,
+        }
+        let on = chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let at = chrono::NaiveTime::from_hms_milli_opt(12, 0, 0, 500).unwrap();
+        let event = Event {
+            on,
+            at,
+            starts: on.and_time(at),
+        };
+
+        let row = event.to_columns().unwrap().into_iter().collect::<Row>();
+        assert_eq!(
+            row.get("starts"),
+            Some(&Value::from("2024-01-31 12:00:00.500"))
+        );
+        assert_eq!(Event::from_row(&row).unwrap(), event);
+
+        let row = Row::from_iter([
+            ("on", "2024-01-31"),
+            ("at", "12:00:00.500"),
+            ("starts", "2024-01-31T12:00:00.500"),
+        ]);
+        assert_eq!(Event::from_row(&row).unwrap(), event);
+    }
+
+    #[test]
+    fn should_read_columns_from_serde_structs() {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct UserDto {
+            user_name: String,
+            #[serde(rename = "mail")]
+            email: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            age: Option<u8>,
+            active: bool,
+        }
+        let user = UserDto {
+            user_name: String::from("John"),
+            email: None,
+            age: None,
+            active: true,
+        };
+        let columns = Serialized::new(&user).unwrap();
+
+        let query = SqlQueryBuilder::table("users")
+            .INSERT(&columns)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO users (userName, mail, active) VALUES ('John', NULL, TRUE);"
+        );
+
+        let key = std::collections::BTreeMap::from([("id", 1)]);
+        let query = SqlQueryBuilder::table("users")
+            .UPDATE(&columns)
+            .WHERE_AND(&Serialized::new(&key).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "UPDATE users SET userName = 'John', active = TRUE WHERE id = 1;"
+        );
+    }
+
+    #[test]
+    fn should_reject_nested_serde_values() {
+        #[derive(serde::Serialize)]
+        struct Post {
+            tags: Vec<String>,
+        }
+
+        let error = Serialized::new(&Post { tags: vec![] }).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Column tags: A sequence cannot be stored in a single column"
+        );
+        let error = Serialized::new(&5).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Only structs and maps can be serialized into columns, found an integer"
+        );
+    }
+
+    #[test]
+    fn should_read_rows_into_structs() {
+        #[derive(Debug, PartialEq, Table, FromRow)]
+        #[table(name = "users", rename_all = "camelCase")]
+        struct User {
+            #[column(primary_key)]
+            id: i64,
+            user_name: String,
+            #[column(name = "mail")]
+            email: Option<String>,
+            active: bool,
+            #[column(skip)]
+            cached: Option<String>,
+        }
+        let user = User {
+            id: 1,
+            user_name: String::from("John"),
+            email: None,
+            active: true,
+            cached: None,
+        };
+
+        let row = user.to_columns().unwrap().into_iter().collect::<Row>();
+        assert_eq!(User::from_row(&row).unwrap(), user);
+
+        let row = Row::from_iter([
+            ("id", Value::Int(1)),
+            ("userName", Value::from("John")),
+            ("mail", Value::Null),
+            ("active", Value::Int(1)),
+        ]);
+        assert_eq!(User::from_row(&row).unwrap(), user);
+    }
+
+    #[test]
+    fn should_report_missing_columns_and_type_mismatches() {
+        #[derive(Debug, FromRow)]
+        struct User {
+            #[allow(dead_code)]
+            id: i32,
+        }
+
+        let error = User::from_row(&Row::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Column id is missing from the row");
+
+        let row = Row::from_iter([("id", "1")]);
+        let error = User::from_row(&row).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Column id: expected i32, found Text(\"1\")"
+        );
+
+        let row = Row::from_iter([("id", Value::Int(i64::MAX))]);
+        let error = User::from_row(&row).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Column id: expected i32, found Int(9223372036854775807)"
+        );
+    }
+
+    mod db {
+        use crate::schema::*;
+
+        crate::schema! {
+            users {
+                id: Integer,
+                name: Text,
+                email: Nullable<Text>,
+            }
+            posts {
+                id: Integer,
+                user_id: Integer,
+                title: Text,
+            }
+        }
+    }
+
+    #[test]
+    fn should_build_queries_from_schema_columns() {
+        use db::{posts, users};
+
+        let query = SqlQueryBuilder::new()
+            .SELECT(&[users::name, posts::title])
+            .FROM(users::table)
+            .JOIN_COND(
+                JoinKind::Inner,
+                posts::table,
+                posts::user_id.compare_column("=", users::id),
+            )
+            .WHERE_COND(Cond::all([
+                users::email.eq("john@example.com"),
+                posts::id.in_list(&[1, 2]),
+                posts::title.like("Rust%"),
+            ]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT users.name, posts.title FROM users JOIN posts ON posts.user_id = users.id WHERE users.email = 'john@example.com' AND posts.id IN (1, 2) AND posts.title LIKE 'Rust%';"
+        );
+
+        let query = SqlQueryBuilder::table(users::table)
+            .UPDATE_AS_SLICE(&[(users::name.name(), "John")])
+            .WHERE_COND(users::id.eq(1))
+            .build()
+            .unwrap();
+        assert_eq!(query, "UPDATE users SET name = 'John' WHERE users.id = 1;");
+
+        let query = SqlQueryBuilder::new()
+            .SELECT(&[users::name.unqualified()])
+            .FROM(users::table.alias("u"))
+            .WHERE_COND(users::id.unqualified().eq(1))
+            .build()
+            .unwrap();
+        assert_eq!(query, "SELECT name FROM users AS u WHERE id = 1;");
+
+        let query = Query::new()
+            .SELECT(&[posts::title])
+            .FROM(posts::table)
+            .JOIN(users::table, users::id, "=", posts::user_id)
+            .ORDER_BY(&[posts::title])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT posts.title FROM posts JOIN users ON users.id = posts.user_id ORDER BY posts.title;"
+        );
+    }
+
+    #[test]
+    fn should_build_statements_through_stages() {
+        let active = Query::new()
+            .SELECT(&["id"])
+            .FROM("users")
+            .WHERE("active", "=", true);
+        let query = Query::new()
+            .SELECT(&["title"])
+            .FROM("posts")
+            .WHERE_IN("user_id", &active)
+            .ORDER_BY(&["title"])
+            .LIMIT(5)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT title FROM posts WHERE user_id IN (SELECT id FROM users WHERE active = TRUE) ORDER BY title LIMIT 5;"
+        );
+
+        let query = Query::<Postgres>::on_table("users")
+            .INSERT_AS_SLICE(&[("id", Value::from(1)), ("name", Value::from("John"))])
+            .ON_CONFLICT(&["id"])
+            .DO_UPDATE(&["name"])
+            .RETURNING(&["id"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "users" ("id", "name") VALUES (1, 'John') ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name" RETURNING "id";"#
+        );
+
+        let query = Query::table("users")
+            .UPDATE_AS_SLICE(&[("name", "John")])
+            .WHERE("id", "=", 1)
+            .build()
+            .unwrap();
+        assert_eq!(query, "UPDATE users SET name = 'John' WHERE id = 1;");
+
+        let query = Query::table("users")
+            .DELETE()
+            .WHERE_NULL("email")
+            .RETURNING(&["id"])
+            .build()
+            .unwrap();
+        assert_eq!(query, "DELETE FROM users WHERE email IS NULL RETURNING id;");
+    }
+
+    #[test]
+    fn should_reject_invalid_operators() {
+        let error = SqlQueryBuilder::table("users")
+            .SELECT(&["*"])
+            .WHERE("a", "= 1 OR 1 =", 1)
+            .build()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid operator: = 1 OR 1 =");
+
+        let error = SqlQueryBuilder::table("users")
+            .SELECT(&["*"])
+            .WHERE_COND(Cond::new("a", "= a; DROP TABLE users; --", 1))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid operator: = a; DROP TABLE users; --"
+        );
+    }
+
+    #[test]
+    fn should_reject_inlined_non_finite_floats() {
+        let query = SqlQueryBuilder::table("readings")
+            .INSERT_AS_SLICE(&[("value", f64::NAN)])
+            .clone();
+
+        let error = query.build().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The non-finite float NaN cannot be inlined, bind it with build_with_params"
+        );
+        let (sql, params) = query.build_with_params().unwrap();
+        assert_eq!(sql, "INSERT INTO readings (value) VALUES ($1);");
+        assert!(matches!(params[..], [Value::Float(float)] if float.is_nan()));
+    }
+
+    #[test]
+    fn should_quote_parenthesised_strings_as_one_identifier() {
+        let query = SqlQueryBuilder::<Postgres>::new()
+            .SELECT(&["1", "2.5", "(price * 2)", "users.*", "1st"])
+            .ORDER_BY(&["(1); DROP TABLE users; SELECT (1)"])
+            .FROM("users")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"SELECT 1, 2.5, "(price * 2)", "users".*, "1st" FROM "users" ORDER BY "(1); DROP TABLE users; SELECT (1)";"#
+        );
+
+        let query = SqlQueryBuilder::<Postgres>::table("users")
+            .DELETE()
+            .RETURNING(&[raw("(price * 2)")])
+            .build()
+            .unwrap();
+        assert_eq!(query, r#"DELETE FROM "users" RETURNING (price * 2);"#);
+
+        let query = SqlQueryBuilder::<MySql>::table("(x); DELETE FROM users; SELECT (1)")
+            .DELETE()
+            .build()
+            .unwrap();
+        assert_eq!(query, "DELETE FROM `(x); DELETE FROM users; SELECT (1)`;");
+
+        let query = SqlQueryBuilder::<Postgres>::new()
+            .SELECT(&["user_id"])
+            .FROM("orders")
+            .GROUP_BY(&["user_id"])
+            .ORDER_BY(&[COUNT("*")])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"SELECT "user_id" FROM "orders" GROUP BY "user_id" ORDER BY COUNT(*);"#
+        );
+    }
+
+    #[test]
+    fn should_keep_the_do_update_condition_apart_from_where() {
+        let query = SqlQueryBuilder::<Postgres>::table("counters")
+            .INSERT_AS_SLICE(&[("name", "visits")])
+            .ON_CONFLICT(&["name"])
+            .DO_UPDATE(&["name"])
+            .DO_UPDATE_WHERE("counters.locked", "=", false)
+            .OR("counters.hits", "<", 10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"INSERT INTO "counters" ("name") VALUES ('visits') ON CONFLICT ("name") DO UPDATE SET "name" = EXCLUDED."name" WHERE "counters"."locked" = FALSE OR "counters"."hits" < 10;"#
+        );
+
+        let error = SqlQueryBuilder::<Postgres>::table("counters")
+            .INSERT_AS_SLICE(&[("name", "visits")])
+            .WHERE("locked", "=", false)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "An INSERT has no WHERE clause, use DO_UPDATE_WHERE to make its update conditional"
+        );
+
+        let error = SqlQueryBuilder::<Postgres>::table("counters")
+            .INSERT_AS_SLICE(&[("name", "visits")])
+            .ON_CONFLICT(&["name"])
+            .DO_NOTHING()
+            .WHERE("locked", "=", false)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "An INSERT has no WHERE clause, use DO_UPDATE_WHERE to make its update conditional"
+        );
+
+        let error = SqlQueryBuilder::<Postgres>::table("counters")
+            .INSERT_AS_SLICE(&[("name", "visits")])
+            .ON_CONFLICT(&["name"])
+            .DO_NOTHING()
+            .DO_UPDATE_WHERE("locked", "=", false)
+            .build()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "DO NOTHING cannot be conditional");
+    }
+
+    #[test]
+    fn should_delete_an_aliased_table_joined_with_others() {
+        let query = SqlQueryBuilder::<MySql>::new()
+            .DELETE()
+            .FROM(("sessions", "s"))
+            .JOIN(("users", "u"), "u.id", "=", "s.user_id")
+            .WHERE("u.banned", "=", true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "DELETE `s` FROM `sessions` AS `s` JOIN `users` AS `u` ON `u`.`id` = `s`.`user_id` WHERE `u`.`banned` = TRUE;"
+        );
+    }
+
+    #[test]
+    fn should_reject_iterable_fields_of_unknown_types() {
+        #[derive(Iterable)]
+        struct Order {
+            id: i32,
+            total: u128,
+        }
+
+        let error = SqlQueryBuilder::table("orders")
+            .INSERT(&Order { id: 1, total: 10 })
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Field total has a type that cannot be converted to a SQL value, derive Table to use its ToSqlValue implementation"
+        );
+
+        let error = SqlQueryBuilder::table("orders")
+            .DELETE_BY(&Order { id: 1, total: 10 })
+            .build()
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn should_update_from_other_tables_through_stages() {
+        let query = Query::<Postgres>::on_table("users")
+            .UPDATE_AS_SLICE(&[("total", col("o.total"))])
+            .FROM(("orders", "o"))
+            .WHERE("o.user_id", "=", col("users.id"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"UPDATE "users" SET "total" = "o"."total" FROM "orders" AS "o" WHERE "o"."user_id" = "users"."id";"#
+        );
+
+        let error = SqlQueryBuilder::<Postgres>::table("users")
+            .UPDATE_AS_SLICE(&[("total", col("o.total"))])
+            .USING(&[("orders", "o")])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "USING only applies to a DELETE, use FROM to add tables to an UPDATE"
+        );
+    }
+
+    #[test]
+    fn should_split_upserts_into_batches_through_stages() {
+        #[derive(Iterable)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        let points = (0..40000)
+            .map(|i| Point { x: i, y: i })
+            .collect::<Vec<Point>>();
+        let sizes = |batches: Vec<(String, Vec<Value>)>| {
+            batches
+                .iter()
+                .map(|(_, params)| params.len())
+                .collect::<Vec<usize>>()
+        };
+
+        let batches = Query::<Postgres>::on_table("points")
+            .INSERT_MANY(&points)
+            .ON_CONFLICT(&["x"])
+            .DO_NOTHING()
+            .build_batches()
+            .unwrap();
+        assert!(batches[1].0.ends_with(r#"ON CONFLICT ("x") DO NOTHING;"#));
+        assert_eq!(sizes(batches), vec![65534, 14466]);
+
+        let batches = Query::<Postgres>::on_table("points")
+            .INSERT_MANY(&points)
+            .ON_CONFLICT(&["x"])
+            .DO_UPDATE(&["y"])
+            .DO_UPDATE_WHERE("points.y", "<>", col("EXCLUDED.y"))
+            .build_batches()
+            .unwrap();
+        assert!(batches[1].0.ends_with(
+            r#"ON CONFLICT ("x") DO UPDATE SET "y" = EXCLUDED."y" WHERE "points"."y" <> "EXCLUDED"."y";"#
+        ));
+        assert_eq!(sizes(batches), vec![65534, 14466]);
+    }
+
+    #[test]
+    fn should_reject_insert_and_update_without_a_table_or_columns() {
+        let error = SqlQueryBuilder::<Postgres>::new()
+            .UPDATE_AS_SLICE(&[("a", 1)])
+            .build()
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), "No table has been provided to UPDATE");
+
+        let error = SqlQueryBuilder::<Postgres>::new()
+            .INSERT_AS_SLICE(&[("a", 1)])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No table has been provided to INSERT INTO"
+        );
+
+        let error = SqlQueryBuilder::table("t")
+            .INSERT_AS_SLICE::<i32>(&[])
+            .build()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "No columns have been provided to INSERT");
+    }
+
+    #[test]
+    fn should_reject_delete_with_a_table_and_a_from_target() {
+        let error = SqlQueryBuilder::table("t")
+            .DELETE()
+            .FROM("other")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "DELETE has both a table and a FROM target, use only one"
+        );
+    }
+
+    #[test]
+    fn should_render_is_null_conditions_without_a_bound_value() {
+        let (query, params) = SqlQueryBuilder::<Postgres>::table("users")
+            .SELECT(&["id"])
+            .WHERE_COND(Cond::new("a", "IS", Value::Null))
+            .AND_COND(Cond::new("b", "is not", Value::Null))
+            .build_with_params()
+            .unwrap();
+        assert_eq!(
+            query,
+            r#"SELECT "id" FROM "users" WHERE "a" IS NULL AND "b" IS NOT NULL;"#
+        );
+        assert!(params.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+//! Reading query results back into structs, the inverse of `ToColumns`.
+
+use std::io;
+
+use crate::Value;
+
+/// A result row: column names and their values, in select-list order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Row {
+    columns: Vec<(String, Value)>,
+}
+
+impl Row {
+    pub fn new() -> Self {
+        Row::default()
+    }
+
+    /// Appends a column to the row.
+    pub fn push<C, V>(&mut self, column: C, value: V)
+    where
+        C: Into<String>,
+        V: Into<Value>,
+    {
+        self.columns.push((column.into(), value.into()));
+    }
+
+    /// Returns the value of a column, `None` when the row does not have it.
+    pub fn get(&self, column: &str) -> Option<&Value> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value)
+    }
+
+    /// Converts the value of a column, failing when the row does not have it
+    /// or when its value does not fit `T`.
+    pub fn get_as<T>(&self, column: &str) -> io::Result<T>
+    where
+        T: FromSqlValue,
+    {
+        let value = self.get(column).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Column {} is missing from the row", column),
+            )
+        })?;
+        T::from_sql_value(value)
+            .map_err(|error| io::Error::new(error.kind(), format!("Column {}: {}", column, error)))
+    }
+}
+
+impl<C, V> FromIterator<(C, V)> for Row
+where
+    C: Into<String>,
+    V: Into<Value>,
+{
+    fn from_iter<I: IntoIterator<Item = (C, V)>>(iter: I) -> Self {
+        let mut row = Row::new();
+        for (column, value) in iter {
+            row.push(column, value);
+        }
+        row
+    }
+}
+
+/// A struct that can be read from a `Row`, usually through
+/// `#[derive(FromRow)]`.
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> io::Result<Self>;
+}
+
+/// Converts a `Value` back into a Rust value, the inverse of `ToSqlValue`.
+pub trait FromSqlValue: Sized {
+    fn from_sql_value(value: &Value) -> io::Result<Self>;
+}
+
+/// The error for a value of the wrong type.
+pub fn mismatch(expected: &str, found: &Value) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("expected {}, found {:?}", expected, found),
+    )
+}
+
+impl FromSqlValue for Value {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<T> FromSqlValue for Option<T>
+where
+    T: FromSqlValue,
+{
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_sql_value(value).map(Some),
+        }
+    }
+}
+
+// Databases without a boolean type store them as 0 and 1.
+impl FromSqlValue for bool {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Bool(value) => Ok(*value),
+            Value::Int(value @ (0 | 1)) => Ok(*value == 1),
+            Value::UInt(value @ (0 | 1)) => Ok(*value == 1),
+            value => Err(mismatch("a bool", value)),
+        }
+    }
+}
+
+macro_rules! impl_from_sql_value_for_int {
+    ($($target:ty),*) => {
+        $(
+            impl FromSqlValue for $target {
+                fn from_sql_value(value: &Value) -> io::Result<Self> {
+                    let converted = match value {
+                        Value::Int(value) => <$target>::try_from(*value).ok(),
+                        Value::UInt(value) => <$target>::try_from(*value).ok(),
+                        _ => None,
+                    };
+                    converted.ok_or_else(|| mismatch(stringify!($target), value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_sql_value_for_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_from_sql_value_for_float {
+    ($($target:ty),*) => {
+        $(
+            impl FromSqlValue for $target {
+                fn from_sql_value(value: &Value) -> io::Result<Self> {
+                    match value {
+                        Value::Float(value) => Ok(*value as $target),
+                        Value::Int(value) => Ok(*value as $target),
+                        Value::UInt(value) => Ok(*value as $target),
+                        value => Err(mismatch(stringify!($target), value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_sql_value_for_float!(f32, f64);
+
+impl FromSqlValue for String {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Text(value) => Ok(value.clone()),
+            value => Err(mismatch("text", value)),
+        }
+    }
+}
+
+impl FromSqlValue for Vec<u8> {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Bytes(value) => Ok(value.clone()),
+            value => Err(mismatch("bytes", value)),
+        }
+    }
+}
+
+/// Implements `FromSqlValue` for types stored as text by parsing it.
+#[allow(unused_macros)]
+macro_rules! impl_from_sql_value_for_text {
+    ($($target:ty),*) => {
+        $(
+            impl FromSqlValue for $target {
+                fn from_sql_value(value: &Value) -> io::Result<Self> {
+                    match value {
+                        Value::Text(text) => text
+                            .parse()
+                            .map_err(|_| mismatch(stringify!($target), value)),
+                        value => Err(mismatch(stringify!($target), value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "chrono")]
+impl_from_sql_value_for_text!(
+    chrono::NaiveDate,
+    chrono::NaiveTime,
+    chrono::DateTime<chrono::Utc>,
+    chrono::DateTime<chrono::FixedOffset>
+);
+
+// Written with a space between date and time, as `ToSqlValue` renders it;
+// the ISO 8601 `T` separator is accepted too.
+#[cfg(feature = "chrono")]
+impl FromSqlValue for chrono::NaiveDateTime {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Text(text) => {
+                chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+                    .or_else(|_| text.parse())
+                    .map_err(|_| mismatch("chrono::NaiveDateTime", value))
+            }
+            value => Err(mismatch("chrono::NaiveDateTime", value)),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl_from_sql_value_for_text!(uuid::Uuid);
+
+#[cfg(feature = "rust_decimal")]
+impl_from_sql_value_for_text!(rust_decimal::Decimal);
+
+#[cfg(feature = "serde_json")]
+impl FromSqlValue for serde_json::Value {
+    fn from_sql_value(value: &Value) -> io::Result<Self> {
+        match value {
+            Value::Text(text) => serde_json::from_str(text).map_err(|_| mismatch("JSON", value)),
+            value => Err(mismatch("JSON", value)),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+//! Typed table and column definitions, declared with the `schema!` macro.
+//!
+//! Columns carry their SQL type, so the conditions built from them only
+//! accept compatible values, and they can be passed wherever the builder
+//! takes a column name or a table.
+//!
+//! Only the conditions built by the column methods, such as `eq`, `like` or
+//! `compare_column`, are type-checked. A column passed to `WHERE`, `JOIN`,
+//! `ORDER_BY`, `SET` or an INSERT as a plain operand is just a name, so
+//! `WHERE(users::id, "=", "not a number")` still compiles.
+//!
+//! A value of another type is rejected:
+//!
+//! ```compile_fail
+//! # use rust_query_builder::schema;
+//! schema! { users { id: Integer } }
+//! users::id.eq("not a number");
+//! ```
+//!
+//! as are text comparisons on other types:
+//!
+//! ```compile_fail
+//! # use rust_query_builder::schema;
+//! schema! { users { id: Integer } }
+//! users::id.like("1%");
+//! ```
+//!
+//! and comparisons between columns of different types:
+//!
+//! ```compile_fail
+//! # use rust_query_builder::schema;
+//! schema! { users { id: Integer, name: Text } }
+//! users::id.compare_column("=", users::name);
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::{Cond, Expr, Operand, TableRef, Value};
+
+/// Declares tables and their columns as modules of constants. Columns of
+/// different types are listed together as `Expr`s:
+///
+/// ```
+/// use rust_query_builder::{schema, Expr, QueryBuilder, SqlQueryBuilder};
+///
+/// schema! {
+///     users {
+///         id: Integer,
+///         name: Text,
+///         email: Nullable<Text>,
+///     }
+/// }
+///
+/// let query = SqlQueryBuilder::new()
+///     .SELECT(&[Expr::from(users::id), Expr::from(users::name)])
+///     .FROM(users::table)
+///     .WHERE_COND(users::id.eq(1))
+///     .build()
+///     .unwrap();
+/// assert_eq!(query, "SELECT users.id, users.name FROM users WHERE users.id = 1;");
+/// ```
+#[macro_export]
+macro_rules! schema {
+    ($(
+        $(#[$meta:meta])*
+        $table:ident {
+            $($column:ident: $sql_type:ty),* $(,)?
+        }
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[allow(non_upper_case_globals)]
+            pub mod $table {
+                #[allow(unused_imports)]
+                use super::*;
+                #[allow(unused_imports)]
+                use $crate::schema::*;
+
+                pub const table: $crate::schema::SqlTable =
+                    $crate::schema::SqlTable::new(stringify!($table));
+                $(
+                    pub const $column: $crate::schema::Column<$sql_type> =
+                        $crate::schema::Column::new(stringify!($table), stringify!($column));
+                )*
+            }
+        )*
+    };
+}
+
+/// A SQL column type. `NotNull` is the type without its nullability, which
+/// is what values are compared against.
+pub trait SqlType {
+    type NotNull: SqlType;
+}
+
+/// Integer columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Integer;
+
+/// Floating point and numeric columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Float;
+
+/// Text columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Text;
+
+/// Boolean columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Bool;
+
+/// Binary columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Bytes;
+
+/// Columns of type `S` that can be `NULL`.
+#[derive(Clone, Copy, Debug)]
+pub struct Nullable<S>(PhantomData<S>);
+
+macro_rules! impl_sql_type {
+    ($($sql_type:ty),*) => {
+        $(
+            impl SqlType for $sql_type {
+                type NotNull = Self;
+            }
+        )*
+    };
+}
+
+impl_sql_type!(Integer, Float, Text, Bool, Bytes);
+
+impl<S> SqlType for Nullable<S>
+where
+    S: SqlType,
+{
+    type NotNull = S::NotNull;
+}
+
+/// A value that can be compared with a column of type `S`.
+pub trait Compatible<S>: Operand
+where
+    S: SqlType,
+{
+}
+
+macro_rules! impl_compatible {
+    ($sql_type:ty, $($source:ty),*) => {
+        $(
+            impl Compatible<$sql_type> for $source {}
+        )*
+    };
+}
+
+impl_compatible!(Integer, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_compatible!(Float, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+impl_compatible!(Text, String, &String, &str);
+impl_compatible!(Bool, bool);
+impl_compatible!(Bytes, Vec<u8>, &[u8]);
+
+/// A table declared with `schema!`.
+#[derive(Clone, Copy, Debug)]
+pub struct SqlTable {
+    name: &'static str,
+}
+
+impl SqlTable {
+    pub const fn new(name: &'static str) -> Self {
+        SqlTable { name }
+    }
+
+    /// Returns the name of the table.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the table under an alias, for `FROM` and joins.
+    pub fn alias(self, alias: &str) -> TableRef {
+        TableRef::from((self.name, alias))
+    }
+}
+
+impl AsRef<str> for SqlTable {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+impl From<SqlTable> for TableRef {
+    fn from(table: SqlTable) -> Self {
+        TableRef::from(table.name)
+    }
+}
+
+/// A column of SQL type `S`, declared with `schema!`. It renders qualified by
+/// its table, `table.column`, so that joined tables sharing column names stay
+/// unambiguous.
+#[derive(Debug)]
+pub struct Column<S> {
+    table: &'static str,
+    name: &'static str,
+    qualified: bool,
+    sql_type: PhantomData<fn() -> S>,
+}
+
+impl<S> Clone for Column<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Column<S> {}
+
+impl<S> Column<S> {
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Column {
+            table,
+            name,
+            qualified: true,
+            sql_type: PhantomData,
+        }
+    }
+
+    /// Returns the name of the column.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the column rendered as its bare name, for a table used under
+    /// an alias or a statement whose dialect rejects qualified names.
+    pub fn unqualified(self) -> Self {
+        Column {
+            qualified: false,
+            ..self
+        }
+    }
+}
+
+impl<S> From<Column<S>> for Expr {
+    fn from(column: Column<S>) -> Self {
+        if column.qualified {
+            Expr::Column(format!("{}.{}", column.table, column.name))
+        } else {
+            Expr::Column(column.name.to_string())
+        }
+    }
+}
+
+impl<S> Column<S>
+where
+    S: SqlType,
+{
+    /// Builds a `column = value` condition.
+    pub fn eq<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, "=", value)
+    }
+
+    /// Builds a `column <> value` condition.
+    pub fn ne<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, "<>", value)
+    }
+
+    /// Builds a `column < value` condition.
+    pub fn lt<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, "<", value)
+    }
+
+    /// Builds a `column <= value` condition.
+    pub fn le<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, "<=", value)
+    }
+
+    /// Builds a `column > value` condition.
+    pub fn gt<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, ">", value)
+    }
+
+    /// Builds a `column >= value` condition.
+    pub fn ge<V>(self, value: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::new(self, ">=", value)
+    }
+
+    /// Builds a `column operator other` comparison with a column of the
+    /// same type.
+    pub fn compare_column<T>(self, operator: &str, other: Column<T>) -> Cond
+    where
+        T: SqlType<NotNull = S::NotNull>,
+    {
+        Cond::new(self, operator, Expr::from(other))
+    }
+
+    /// Builds a `column IN (values...)` condition.
+    pub fn in_list<V>(self, values: &[V]) -> Cond
+    where
+        V: Compatible<S::NotNull> + Into<Value> + Clone,
+    {
+        Cond::in_list(self, values)
+    }
+
+    /// Builds a `column BETWEEN low AND high` condition.
+    pub fn between<V>(self, low: V, high: V) -> Cond
+    where
+        V: Compatible<S::NotNull>,
+    {
+        Cond::between(self, low, high)
+    }
+
+    /// Builds a `column IS NULL` condition.
+    pub fn is_null(self) -> Cond {
+        Cond::is_null(self)
+    }
+
+    /// Builds a `column IS NOT NULL` condition.
+    pub fn is_not_null(self) -> Cond {
+        Cond::is_not_null(self)
+    }
+}
+
+impl<S> Column<S>
+where
+    S: SqlType<NotNull = Text>,
+{
+    /// Builds a `column LIKE pattern` condition.
+    pub fn like(self, pattern: &str) -> Cond {
+        Cond::like(self, pattern)
+    }
+}
@@ -0,0 +1,482 @@
+//! Reads columns from any `T: Serialize` through a `serde::Serializer`, so
+//! serde-annotated structs can be used with struct-driven methods. Field
+//! names follow `#[serde(rename)]` and `rename_all`, and fields skipped by
+//! `skip_serializing_if` are left out.
+
+use std::fmt::{self, Display};
+use std::io;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::{ToColumns, Value};
+
+/// The columns of a serialized value, in serialization order, e.g.
+/// `builder.INSERT(&Serialized::new(&dto)?)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Serialized {
+    columns: Vec<(String, Value)>,
+}
+
+impl Serialized {
+    /// Serializes a struct or map into columns. Fails if the value is not a
+    /// struct or map, or if one of its fields is a sequence, a map, a struct
+    /// or a non-unit enum variant.
+    pub fn new<T>(value: &T) -> io::Result<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut columns = Vec::new();
+        value
+            .serialize(ColumnSerializer {
+                columns: &mut columns,
+            })
+            .map_err(|Error(message)| io::Error::new(io::ErrorKind::InvalidInput, message))?;
+        Ok(Serialized { columns })
+    }
+}
+
+impl ToColumns for Serialized {
+    fn to_columns(&self) -> io::Result<Vec<(String, Value)>> {
+        Ok(self.columns.clone())
+    }
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Error(message.to_string())
+    }
+}
+
+fn unsupported<T>(found: &str) -> Result<T, Error> {
+    Err(Error(format!(
+        "Only structs and maps can be serialized into columns, found {}",
+        found
+    )))
+}
+
+/// Serializes the top-level struct or map, one column per field.
+struct ColumnSerializer<'a> {
+    columns: &'a mut Vec<(String, Value)>,
+}
+
+impl<'a> ser::Serializer for ColumnSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = MapColumns<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, _: bool) -> Result<(), Error> {
+        unsupported("a bool")
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<(), Error> {
+        unsupported("an integer")
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<(), Error> {
+        unsupported("a float")
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<(), Error> {
+        unsupported("a float")
+    }
+
+    fn serialize_char(self, _: char) -> Result<(), Error> {
+        unsupported("a char")
+    }
+
+    fn serialize_str(self, _: &str) -> Result<(), Error> {
+        unsupported("a string")
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Error> {
+        unsupported("bytes")
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        unsupported("None")
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        unsupported("()")
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        unsupported(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        unsupported(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: &T,
+    ) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        unsupported(variant)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        unsupported("a sequence")
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        unsupported("a tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported(name)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported(variant)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(MapColumns {
+            columns: self.columns,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        unsupported(variant)
+    }
+}
+
+impl ser::SerializeStruct for ColumnSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = column_value(key, value)?;
+        self.columns.push((key.to_string(), value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Serializes a map, or a struct with `#[serde(flatten)]` fields, whose keys
+/// must be strings.
+struct MapColumns<'a> {
+    columns: &'a mut Vec<(String, Value)>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapColumns<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match key.serialize(ValueSerializer)? {
+            Value::Text(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            key => Err(Error(format!(
+                "Column names must be strings, found {:?}",
+                key
+            ))),
+        }
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error(String::from("A map value was serialized before its key")))?;
+        let value = column_value(&key, value)?;
+        self.columns.push((key, value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn column_value<T>(column: &str, value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value
+        .serialize(ValueSerializer)
+        .map_err(|Error(message)| Error(format!("Column {}: {}", column, message)))
+}
+
+fn nested<T>(found: &str) -> Result<T, Error> {
+    Err(Error(format!(
+        "{} cannot be stored in a single column",
+        found
+    )))
+}
+
+/// Serializes a single field into a `Value`.
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = Impossible<Value, Error>;
+    type SerializeTuple = Impossible<Value, Error>;
+    type SerializeTupleStruct = Impossible<Value, Error>;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = Impossible<Value, Error>;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    fn serialize_bool(self, value: bool) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Value, Error> {
+        Ok(Value::Text(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value, Error> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    // Unit variants are stored by name, the way serde represents them
+    // everywhere else.
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::from(variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: &T,
+    ) -> Result<Value, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        nested(&format!("Enum variant {}", variant))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        nested("A sequence")
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        nested("A tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        nested(&format!("Struct {}", name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        nested(&format!("Enum variant {}", variant))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        nested("A map")
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        nested(&format!("Struct {}", name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        nested(&format!("Enum variant {}", variant))
+    }
+}
@@ -0,0 +1,871 @@
+//! A typestate front end to `SqlQueryBuilder`. Each statement starts from
+//! `Query` and moves through stage types that only expose the clauses valid
+//! for it:
+//!
+//! ```
+//! use rust_query_builder::Query;
+//!
+//! let query = Query::table("users")
+//!     .INSERT_AS_SLICE(&[("id", 1)])
+//!     .ON_CONFLICT(&["id"])
+//!     .DO_NOTHING()
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(query, "INSERT INTO users (id) VALUES (1) ON CONFLICT (id) DO NOTHING;");
+//! ```
+//!
+//! So `LIMIT` before `SELECT` does not compile:
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::new().LIMIT(5);
+//! ```
+//!
+//! nor an INSERT, UPDATE or DELETE without a table:
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::new().INSERT_AS_SLICE(&[("id", 1)]);
+//! ```
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::new().UPDATE_AS_SLICE(&[("id", 1)]);
+//! ```
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::new().DELETE();
+//! ```
+//!
+//! nor a WHERE on a plain INSERT:
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::table("users")
+//!     .INSERT_AS_SLICE(&[("id", 1)])
+//!     .WHERE("id", "=", 1);
+//! ```
+//!
+//! nor a DO UPDATE without a conflict target:
+//!
+//! ```compile_fail
+//! rust_query_builder::Query::table("users")
+//!     .INSERT_AS_SLICE(&[("id", 1)])
+//!     .DO_UPDATE(&["id"]);
+//! ```
+
+use std::io;
+
+use crate::{
+    Cond, CteName, Dialect, Expr, Generic, JoinKind, Operand, QueryBuilder, SqlQueryBuilder,
+    Subquery, Table, TableRef, ToColumns, Value,
+};
+
+/// Adds the WHERE family of methods to a stage.
+macro_rules! condition_methods {
+    ($stage:ident) => {
+        #[allow(non_snake_case)]
+        impl<D: Dialect> $stage<D> {
+            /// Adds a WHERE clause.
+            pub fn WHERE<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.WHERE(operand, operator, result);
+                self
+            }
+
+            /// Adds a WHERE NOT clause.
+            pub fn WHERE_NOT<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.WHERE_NOT(operand, operator, result);
+                self
+            }
+
+            /// Adds an AND clause.
+            pub fn AND<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.AND(operand, operator, result);
+                self
+            }
+
+            /// Adds an AND NOT clause.
+            pub fn AND_NOT<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.AND_NOT(operand, operator, result);
+                self
+            }
+
+            /// Adds an OR clause.
+            pub fn OR<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.OR(operand, operator, result);
+                self
+            }
+
+            /// Adds an OR NOT clause.
+            pub fn OR_NOT<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.OR_NOT(operand, operator, result);
+                self
+            }
+
+            /// Adds a WHERE clause with a grouped condition.
+            pub fn WHERE_COND<C>(mut self, condition: C) -> Self
+            where
+                C: Into<Cond>,
+            {
+                self.query.WHERE_COND(condition);
+                self
+            }
+
+            /// ANDs a grouped condition onto the WHERE clause.
+            pub fn AND_COND<C>(mut self, condition: C) -> Self
+            where
+                C: Into<Cond>,
+            {
+                self.query.AND_COND(condition);
+                self
+            }
+
+            /// ORs a grouped condition onto the WHERE clause.
+            pub fn OR_COND<C>(mut self, condition: C) -> Self
+            where
+                C: Into<Cond>,
+            {
+                self.query.OR_COND(condition);
+                self
+            }
+
+            /// Adds a WHERE clause matching the non-null columns of a struct.
+            pub fn WHERE_AND<T>(mut self, columns: &T) -> Self
+            where
+                T: ToColumns,
+            {
+                self.query.WHERE_AND(columns);
+                self
+            }
+
+            /// Adds a WHERE ... BETWEEN clause.
+            pub fn WHERE_BETWEEN<E, V>(mut self, operand: E, low: V, high: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.WHERE_BETWEEN(operand, low, high);
+                self
+            }
+
+            /// Adds a WHERE ... IN (values) clause.
+            pub fn WHERE_IN_LIST<E, V>(mut self, operand: E, values: &[V]) -> Self
+            where
+                E: Into<Expr>,
+                V: Into<Value> + Clone,
+            {
+                self.query.WHERE_IN_LIST(operand, values);
+                self
+            }
+
+            /// Adds a WHERE ... NOT IN (values) clause.
+            pub fn WHERE_NOT_IN_LIST<E, V>(mut self, operand: E, values: &[V]) -> Self
+            where
+                E: Into<Expr>,
+                V: Into<Value> + Clone,
+            {
+                self.query.WHERE_NOT_IN_LIST(operand, values);
+                self
+            }
+
+            /// Adds a WHERE ... LIKE clause.
+            pub fn WHERE_LIKE<E, V>(mut self, operand: E, pattern: V) -> Self
+            where
+                E: Into<Expr>,
+                V: Operand,
+            {
+                self.query.WHERE_LIKE(operand, pattern);
+                self
+            }
+
+            /// Adds a WHERE ... IS NULL clause.
+            pub fn WHERE_NULL<E>(mut self, operand: E) -> Self
+            where
+                E: Into<Expr>,
+            {
+                self.query.WHERE_NULL(operand);
+                self
+            }
+
+            /// Adds a WHERE ... IS NOT NULL clause.
+            pub fn WHERE_NOT_NULL<E>(mut self, operand: E) -> Self
+            where
+                E: Into<Expr>,
+            {
+                self.query.WHERE_NOT_NULL(operand);
+                self
+            }
+
+            /// Adds a WHERE ... IN (subquery) clause.
+            pub fn WHERE_IN<E>(mut self, operand: E, subquery: &SelectStage<D>) -> Self
+            where
+                E: Into<Expr>,
+            {
+                self.query.WHERE_IN(operand, &subquery.query);
+                self
+            }
+
+            /// Adds a WHERE ... NOT IN (subquery) clause.
+            pub fn WHERE_NOT_IN<E>(mut self, operand: E, subquery: &SelectStage<D>) -> Self
+            where
+                E: Into<Expr>,
+            {
+                self.query.WHERE_NOT_IN(operand, &subquery.query);
+                self
+            }
+
+            /// Adds a WHERE EXISTS (subquery) clause.
+            pub fn WHERE_EXISTS(mut self, subquery: &SelectStage<D>) -> Self {
+                self.query.WHERE_EXISTS(&subquery.query);
+                self
+            }
+
+            /// Adds a WHERE NOT EXISTS (subquery) clause.
+            pub fn WHERE_NOT_EXISTS(mut self, subquery: &SelectStage<D>) -> Self {
+                self.query.WHERE_NOT_EXISTS(&subquery.query);
+                self
+            }
+        }
+    };
+}
+
+/// Adds the JOIN family of methods to a stage.
+macro_rules! join_methods {
+    ($stage:ident) => {
+        #[allow(non_snake_case)]
+        impl<D: Dialect> $stage<D> {
+            /// Adds a JOIN clause.
+            pub fn JOIN<T, L, R>(mut self, table: T, column1: L, operator: &str, column2: R) -> Self
+            where
+                T: Into<TableRef>,
+                L: Into<Expr>,
+                R: Into<Expr>,
+            {
+                self.query.JOIN(table, column1, operator, column2);
+                self
+            }
+
+            /// Adds a LEFT JOIN clause.
+            pub fn LEFT_JOIN<T, L, R>(
+                mut self,
+                table: T,
+                column1: L,
+                operator: &str,
+                column2: R,
+            ) -> Self
+            where
+                T: Into<TableRef>,
+                L: Into<Expr>,
+                R: Into<Expr>,
+            {
+                self.query.LEFT_JOIN(table, column1, operator, column2);
+                self
+            }
+
+            /// Adds a RIGHT JOIN clause.
+            pub fn RIGHT_JOIN<T, L, R>(
+                mut self,
+                table: T,
+                column1: L,
+                operator: &str,
+                column2: R,
+            ) -> Self
+            where
+                T: Into<TableRef>,
+                L: Into<Expr>,
+                R: Into<Expr>,
+            {
+                self.query.RIGHT_JOIN(table, column1, operator, column2);
+                self
+            }
+
+            /// Adds a FULL JOIN clause.
+            pub fn FULL_JOIN<T, L, R>(
+                mut self,
+                table: T,
+                column1: L,
+                operator: &str,
+                column2: R,
+            ) -> Self
+            where
+                T: Into<TableRef>,
+                L: Into<Expr>,
+                R: Into<Expr>,
+            {
+                self.query.FULL_JOIN(table, column1, operator, column2);
+                self
+            }
+
+            /// Adds a CROSS JOIN clause.
+            pub fn CROSS_JOIN<T>(mut self, table: T) -> Self
+            where
+                T: Into<TableRef>,
+            {
+                self.query.CROSS_JOIN(table);
+                self
+            }
+
+            /// Adds a join of any kind with a grouped ON condition.
+            pub fn JOIN_COND<T, C>(mut self, kind: JoinKind, table: T, on: C) -> Self
+            where
+                T: Into<TableRef>,
+                C: Into<Cond>,
+            {
+                self.query.JOIN_COND(kind, table, on);
+                self
+            }
+
+            /// Adds a join of any kind with ANDed column comparisons.
+            pub fn JOIN_ON<T>(mut self, kind: JoinKind, table: T, on: &[(&str, &str, &str)]) -> Self
+            where
+                T: Into<TableRef>,
+            {
+                self.query.JOIN_ON(kind, table, on);
+                self
+            }
+
+            /// Adds a join of any kind with a USING (columns) clause.
+            pub fn JOIN_USING<T>(mut self, kind: JoinKind, table: T, columns: &[&str]) -> Self
+            where
+                T: Into<TableRef>,
+            {
+                self.query.JOIN_USING(kind, table, columns);
+                self
+            }
+        }
+    };
+}
+
+/// Adds `build` and `build_with_params` to a stage.
+macro_rules! build_methods {
+    ($stage:ident) => {
+        impl<D: Dialect> $stage<D> {
+            /// Builds the SQL query with inlined values.
+            pub fn build(&self) -> io::Result<String> {
+                self.query.build()
+            }
+
+            /// Builds the SQL query with placeholders, returning it together
+            /// with the values to bind, in order.
+            pub fn build_with_params(&self) -> io::Result<(String, Vec<Value>)> {
+                self.query.build_with_params()
+            }
+        }
+    };
+}
+
+/// Adds `build_batches` to the stages of an INSERT.
+macro_rules! build_batches_method {
+    ($stage:ident) => {
+        impl<D: Dialect> $stage<D> {
+            /// Builds a multi-row INSERT as as many statements as the dialect's
+            /// limits require.
+            pub fn build_batches(&self) -> io::Result<Vec<(String, Vec<Value>)>> {
+                self.query.build_batches()
+            }
+        }
+    };
+}
+
+/// Adds `RETURNING`, which keeps the statement in the same stage.
+macro_rules! returning_method {
+    ($stage:ident) => {
+        #[allow(non_snake_case)]
+        impl<D: Dialect> $stage<D> {
+            /// Adds a RETURNING clause.
+            pub fn RETURNING<C>(mut self, columns: &[C]) -> Self
+            where
+                C: Into<Expr> + Clone,
+            {
+                self.query.RETURNING(columns);
+                self
+            }
+        }
+    };
+}
+
+/// Adds the WITH family of methods to a stage.
+macro_rules! with_methods {
+    ($stage:ident) => {
+        #[allow(non_snake_case)]
+        impl<D: Dialect> $stage<D> {
+            /// Adds a common table expression to the WITH clause.
+            pub fn WITH<N>(mut self, name: N, query: &SelectStage<D>) -> Self
+            where
+                N: Into<CteName>,
+            {
+                self.query.WITH(name, &query.query);
+                self
+            }
+
+            /// Adds a common table expression with a MATERIALIZED hint.
+            pub fn WITH_MATERIALIZED<N>(mut self, name: N, query: &SelectStage<D>) -> Self
+            where
+                N: Into<CteName>,
+            {
+                self.query.WITH_MATERIALIZED(name, &query.query);
+                self
+            }
+
+            /// Adds a common table expression with a NOT MATERIALIZED hint.
+            pub fn WITH_NOT_MATERIALIZED<N>(mut self, name: N, query: &SelectStage<D>) -> Self
+            where
+                N: Into<CteName>,
+            {
+                self.query.WITH_NOT_MATERIALIZED(name, &query.query);
+                self
+            }
+
+            /// Adds a common table expression and turns the clause into WITH RECURSIVE.
+            pub fn WITH_RECURSIVE<N>(mut self, name: N, query: &SelectStage<D>) -> Self
+            where
+                N: Into<CteName>,
+            {
+                self.query.WITH_RECURSIVE(name, &query.query);
+                self
+            }
+        }
+    };
+}
+
+/// Adds the transitions to `SelectStage` to a stage.
+macro_rules! select_methods {
+    ($stage:ident) => {
+        #[allow(non_snake_case)]
+        impl<D: Dialect> $stage<D> {
+            /// Starts a SELECT.
+            pub fn SELECT<C>(mut self, columns: &[C]) -> SelectStage<D>
+            where
+                C: Into<Expr> + Clone,
+            {
+                self.query.SELECT(columns);
+                SelectStage { query: self.query }
+            }
+
+            /// Starts a SELECT DISTINCT.
+            pub fn SELECT_DISTINCT<C>(mut self, columns: &[C]) -> SelectStage<D>
+            where
+                C: Into<Expr> + Clone,
+            {
+                self.query.SELECT_DISTINCT(columns);
+                SelectStage { query: self.query }
+            }
+        }
+    };
+}
+
+/// The start of a statement: common table expressions and a SELECT. INSERT,
+/// UPDATE and DELETE need a table first, see `Query::table`.
+#[derive(Clone, Debug, Default)]
+pub struct Query<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+impl Query {
+    /// Starts a statement for the `Generic` dialect.
+    /// Use `Query::<D>::default()` to target another dialect.
+    pub fn new() -> Self {
+        <Query<Generic>>::default()
+    }
+
+    /// Starts a statement on a table for the `Generic` dialect.
+    pub fn table<T>(table: T) -> TableStage
+    where
+        T: AsRef<str>,
+    {
+        Query::<Generic>::on_table(table)
+    }
+
+    /// Starts a statement on the table of a `Table` struct for the
+    /// `Generic` dialect.
+    pub fn table_of<T>() -> TableStage
+    where
+        T: Table,
+    {
+        Query::<Generic>::on_table(T::TABLE_NAME)
+    }
+}
+
+impl<D: Dialect> Query<D> {
+    /// Starts a statement on a table, for any dialect.
+    pub fn on_table<T>(table: T) -> TableStage<D>
+    where
+        T: AsRef<str>,
+    {
+        TableStage {
+            query: SqlQueryBuilder::<D>::table(table),
+        }
+    }
+}
+
+with_methods!(Query);
+select_methods!(Query);
+
+/// A statement on a table, before the choice between SELECT, INSERT, UPDATE
+/// and DELETE.
+#[derive(Clone, Debug)]
+pub struct TableStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> TableStage<D> {
+    /// Starts an INSERT of the columns of a struct.
+    pub fn INSERT<T>(mut self, columns: &T) -> InsertStage<D>
+    where
+        T: ToColumns,
+    {
+        self.query.INSERT(columns);
+        InsertStage { query: self.query }
+    }
+
+    /// Starts a multi-row INSERT from a slice of structs.
+    pub fn INSERT_MANY<T>(mut self, rows: &[T]) -> InsertStage<D>
+    where
+        T: ToColumns,
+    {
+        self.query.INSERT_MANY(rows);
+        InsertStage { query: self.query }
+    }
+
+    /// Starts an INSERT of the given columns and values.
+    pub fn INSERT_AS_SLICE<V>(mut self, columns_and_values: &[(&str, V)]) -> InsertStage<D>
+    where
+        V: Into<Value> + Clone,
+    {
+        self.query.INSERT_AS_SLICE(columns_and_values);
+        InsertStage { query: self.query }
+    }
+
+    /// Starts an INSERT of the rows returned by a SELECT.
+    pub fn INSERT_FROM_SELECT(
+        mut self,
+        columns: &[&str],
+        query: &SelectStage<D>,
+    ) -> InsertStage<D> {
+        self.query.INSERT_FROM_SELECT(columns, &query.query);
+        InsertStage { query: self.query }
+    }
+
+    /// Starts an UPDATE setting the columns of a struct.
+    pub fn UPDATE<T>(mut self, columns: &T) -> UpdateStage<D>
+    where
+        T: ToColumns,
+    {
+        self.query.UPDATE(columns);
+        UpdateStage { query: self.query }
+    }
+
+    /// Starts an UPDATE setting the given columns to values or expressions.
+    pub fn UPDATE_AS_SLICE<V>(mut self, columns_and_values: &[(&str, V)]) -> UpdateStage<D>
+    where
+        V: Operand + Clone,
+    {
+        self.query.UPDATE_AS_SLICE(columns_and_values);
+        UpdateStage { query: self.query }
+    }
+
+    /// Starts a DELETE.
+    pub fn DELETE(mut self) -> DeleteStage<D> {
+        self.query.DELETE();
+        DeleteStage { query: self.query }
+    }
+
+    /// Starts a DELETE of the rows matching the non-null columns of a struct.
+    pub fn DELETE_BY<T>(mut self, columns: &T) -> DeleteStage<D>
+    where
+        T: ToColumns,
+    {
+        self.query.DELETE_BY(columns);
+        DeleteStage { query: self.query }
+    }
+}
+
+with_methods!(TableStage);
+select_methods!(TableStage);
+
+/// A SELECT statement.
+#[derive(Clone, Debug)]
+pub struct SelectStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> SelectStage<D> {
+    /// Adds a FROM clause. Accepts `"table"` or `("table", "alias")`.
+    pub fn FROM<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.query.FROM(table);
+        self
+    }
+
+    /// Adds a GROUP BY clause.
+    pub fn GROUP_BY<C>(mut self, columns: &[C]) -> Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        self.query.GROUP_BY(columns);
+        self
+    }
+
+    /// Adds a HAVING clause. Following AND and OR calls extend it.
+    pub fn HAVING<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.query.HAVING(operand, operator, result);
+        self
+    }
+
+    /// Adds a HAVING clause with a grouped condition.
+    pub fn HAVING_COND<C>(mut self, condition: C) -> Self
+    where
+        C: Into<Cond>,
+    {
+        self.query.HAVING_COND(condition);
+        self
+    }
+
+    /// Adds an ORDER BY clause.
+    pub fn ORDER_BY<C>(mut self, columns: &[C]) -> Self
+    where
+        C: Into<Expr> + Clone,
+    {
+        self.query.ORDER_BY(columns);
+        self
+    }
+
+    /// Adds a LIMIT clause.
+    pub fn LIMIT(mut self, limit: u32) -> Self {
+        self.query.LIMIT(limit);
+        self
+    }
+
+    /// Adds an OFFSET clause.
+    pub fn OFFSET(mut self, offset: u32) -> Self {
+        self.query.OFFSET(offset);
+        self
+    }
+
+    /// Combines the result with the distinct rows of another SELECT.
+    pub fn UNION(mut self, query: &SelectStage<D>) -> Self {
+        self.query.UNION(&query.query);
+        self
+    }
+
+    /// Combines the result with all rows of another SELECT.
+    pub fn UNION_ALL(mut self, query: &SelectStage<D>) -> Self {
+        self.query.UNION_ALL(&query.query);
+        self
+    }
+
+    /// Keeps the rows also returned by another SELECT.
+    pub fn INTERSECT(mut self, query: &SelectStage<D>) -> Self {
+        self.query.INTERSECT(&query.query);
+        self
+    }
+
+    /// Removes the rows of another SELECT from the result.
+    pub fn EXCEPT(mut self, query: &SelectStage<D>) -> Self {
+        self.query.EXCEPT(&query.query);
+        self
+    }
+}
+
+condition_methods!(SelectStage);
+join_methods!(SelectStage);
+build_methods!(SelectStage);
+
+impl<D: Dialect> From<&SelectStage<D>> for Subquery {
+    fn from(query: &SelectStage<D>) -> Self {
+        Subquery::from(&query.query)
+    }
+}
+
+impl<D: Dialect> From<&SelectStage<D>> for Expr {
+    fn from(query: &SelectStage<D>) -> Self {
+        Expr::from(&query.query)
+    }
+}
+
+impl<D: Dialect> From<(&SelectStage<D>, &str)> for TableRef {
+    fn from((query, alias): (&SelectStage<D>, &str)) -> Self {
+        TableRef::from((&query.query, alias))
+    }
+}
+
+/// An INSERT statement, before any conflict handling.
+#[derive(Clone, Debug)]
+pub struct InsertStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> InsertStage<D> {
+    /// Handles conflicts on the given columns. MySQL does not render them,
+    /// as it updates on any unique key.
+    pub fn ON_CONFLICT(mut self, columns: &[&str]) -> ConflictStage<D> {
+        self.query.ON_CONFLICT(columns);
+        ConflictStage { query: self.query }
+    }
+
+    /// Handles conflicts on the given constraint.
+    pub fn ON_CONFLICT_ON_CONSTRAINT(mut self, constraint: &str) -> ConflictStage<D> {
+        self.query.ON_CONFLICT_ON_CONSTRAINT(constraint);
+        ConflictStage { query: self.query }
+    }
+
+    /// Skips conflicting rows, without a conflict target.
+    pub fn DO_NOTHING(mut self) -> UpsertStage<D> {
+        self.query.DO_NOTHING();
+        UpsertStage { query: self.query }
+    }
+}
+
+returning_method!(InsertStage);
+build_methods!(InsertStage);
+build_batches_method!(InsertStage);
+
+/// An INSERT with a conflict target, waiting for its action.
+#[derive(Clone, Debug)]
+pub struct ConflictStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> ConflictStage<D> {
+    /// Skips the conflicting rows.
+    pub fn DO_NOTHING(mut self) -> UpsertStage<D> {
+        self.query.DO_NOTHING();
+        UpsertStage { query: self.query }
+    }
+
+    /// Updates the given columns of conflicting rows with the values the
+    /// INSERT proposed.
+    pub fn DO_UPDATE(mut self, columns: &[&str]) -> DoUpdateStage<D> {
+        self.query.DO_UPDATE(columns);
+        DoUpdateStage { query: self.query }
+    }
+
+    /// Updates conflicting rows with the given values or expressions.
+    pub fn DO_UPDATE_SET<V>(mut self, columns_and_values: &[(&str, V)]) -> DoUpdateStage<D>
+    where
+        V: Operand + Clone,
+    {
+        self.query.DO_UPDATE_SET(columns_and_values);
+        DoUpdateStage { query: self.query }
+    }
+}
+
+/// An INSERT ... DO UPDATE, whose update can be made conditional.
+#[derive(Clone, Debug)]
+pub struct DoUpdateStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> DoUpdateStage<D> {
+    /// Only updates the conflicting rows matching the condition.
+    pub fn DO_UPDATE_WHERE<E, V>(mut self, operand: E, operator: &str, result: V) -> Self
+    where
+        E: Into<Expr>,
+        V: Operand,
+    {
+        self.query.DO_UPDATE_WHERE(operand, operator, result);
+        self
+    }
+
+    /// Only updates the conflicting rows matching a grouped condition.
+    pub fn DO_UPDATE_WHERE_COND<C>(mut self, condition: C) -> Self
+    where
+        C: Into<Cond>,
+    {
+        self.query.DO_UPDATE_WHERE_COND(condition);
+        self
+    }
+}
+
+returning_method!(DoUpdateStage);
+build_methods!(DoUpdateStage);
+build_batches_method!(DoUpdateStage);
+
+/// An INSERT with complete conflict handling.
+#[derive(Clone, Debug)]
+pub struct UpsertStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+returning_method!(UpsertStage);
+build_methods!(UpsertStage);
+build_batches_method!(UpsertStage);
+
+/// An UPDATE statement.
+#[derive(Clone, Debug)]
+pub struct UpdateStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> UpdateStage<D> {
+    /// Adds another table to the UPDATE, rendered as FROM or as a table list
+    /// depending on the dialect. Accepts `"table"` or `("table", "alias")`.
+    pub fn FROM<T>(mut self, table: T) -> Self
+    where
+        T: Into<TableRef>,
+    {
+        self.query.FROM(table);
+        self
+    }
+}
+
+condition_methods!(UpdateStage);
+join_methods!(UpdateStage);
+returning_method!(UpdateStage);
+build_methods!(UpdateStage);
+
+/// A DELETE statement.
+#[derive(Clone, Debug)]
+pub struct DeleteStage<D: Dialect = Generic> {
+    query: SqlQueryBuilder<D>,
+}
+
+#[allow(non_snake_case)]
+impl<D: Dialect> DeleteStage<D> {
+    /// Adds other tables to the DELETE, rendered as USING or FROM depending
+    /// on the dialect.
+    pub fn USING<T>(mut self, tables: &[T]) -> Self
+    where
+        T: Into<TableRef> + Clone,
+    {
+        self.query.USING(tables);
+        self
+    }
+}
+
+condition_methods!(DeleteStage);
+join_methods!(DeleteStage);
+returning_method!(DeleteStage);
+build_methods!(DeleteStage);
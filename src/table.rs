@@ -0,0 +1,43 @@
+//! The traits struct-driven methods such as `INSERT`, `UPDATE` and
+//! `WHERE_AND` read columns from.
+
+use std::io;
+
+use crate::iterate_struct::iterate_struct;
+use crate::{Iterable, Value};
+
+/// A value that provides column/value pairs, in declaration order.
+/// Implemented by `#[derive(Table)]` and, through `iterate_struct`, by every
+/// `Iterable`. Reading fails on a value that has no SQL representation, and
+/// the builder returns the error from `build`.
+pub trait ToColumns {
+    /// Every column, as read by `WHERE_AND` and `DELETE_BY`.
+    fn to_columns(&self) -> io::Result<Vec<(String, Value)>>;
+    /// The columns `INSERT` and `INSERT_MANY` write.
+    fn insert_columns(&self) -> io::Result<Vec<(String, Value)>> {
+        self.to_columns()
+    }
+    /// The columns `UPDATE` sets.
+    fn update_columns(&self) -> io::Result<Vec<(String, Value)>> {
+        self.to_columns()
+    }
+}
+
+impl<T> ToColumns for T
+where
+    T: Iterable,
+{
+    fn to_columns(&self) -> io::Result<Vec<(String, Value)>> {
+        iterate_struct(self)
+    }
+}
+
+/// A struct mapped to a table, usually through `#[derive(Table)]`.
+pub trait Table: ToColumns {
+    /// Name of the table.
+    const TABLE_NAME: &'static str;
+    /// Names of the columns, in declaration order.
+    const COLUMNS: &'static [&'static str];
+    /// Names of the primary key columns.
+    const PRIMARY_KEY: &'static [&'static str];
+}
@@ -0,0 +1,213 @@
+/// A typed SQL value, rendered according to its type instead of always
+/// being quoted as a string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Value {
+    /// Returns `true` if the value is `NULL`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Value {
+                fn from(value: $source) -> Self {
+                    Value::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Int, i64, i8, i16, i32, i64, isize, u8, u16, u32);
+impl_from_int!(UInt, u64, u64, usize);
+impl_from_int!(Float, f64, f32, f64);
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::Text(value.clone())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Value::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(value: &[u8]) -> Self {
+        Value::Bytes(value.to_vec())
+    }
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Value::Null,
+        }
+    }
+}
+
+/// Converts a Rust value into a `Value`. Struct-driven methods read fields
+/// through it, so implementing it for your own types lets them be used as
+/// columns of a `#[derive(Table)]` struct.
+pub trait ToSqlValue {
+    fn to_sql_value(&self) -> Value;
+}
+
+macro_rules! impl_to_sql_value_for_copy {
+    ($($source:ty),*) => {
+        $(
+            impl ToSqlValue for $source {
+                fn to_sql_value(&self) -> Value {
+                    Value::from(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_sql_value_for_copy!(bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl ToSqlValue for str {
+    fn to_sql_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToSqlValue for String {
+    fn to_sql_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToSqlValue for [u8] {
+    fn to_sql_value(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToSqlValue for Vec<u8> {
+    fn to_sql_value(&self) -> Value {
+        Value::from(self.as_slice())
+    }
+}
+
+impl ToSqlValue for Value {
+    fn to_sql_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl<T> ToSqlValue for Option<T>
+where
+    T: ToSqlValue,
+{
+    fn to_sql_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_sql_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T> ToSqlValue for &T
+where
+    T: ToSqlValue + ?Sized,
+{
+    fn to_sql_value(&self) -> Value {
+        (**self).to_sql_value()
+    }
+}
+
+/// Implements `From` and `ToSqlValue` for types rendered as text.
+#[allow(unused_macros)]
+macro_rules! impl_text_value {
+    ($($source:ty),*) => {
+        $(
+            impl From<$source> for Value {
+                fn from(value: $source) -> Self {
+                    Value::Text(value.to_string())
+                }
+            }
+
+            impl ToSqlValue for $source {
+                fn to_sql_value(&self) -> Value {
+                    Value::Text(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+// Dates and times as ISO 8601 text, e.g. `2024-01-31 12:00:00`.
+#[cfg(feature = "chrono")]
+impl_text_value!(chrono::NaiveDate, chrono::NaiveTime, chrono::NaiveDateTime);
+
+#[cfg(feature = "chrono")]
+impl<Tz> From<chrono::DateTime<Tz>> for Value
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        value.to_sql_value()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz> ToSqlValue for chrono::DateTime<Tz>
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn to_sql_value(&self) -> Value {
+        Value::Text(self.to_rfc3339())
+    }
+}
+
+// UUIDs in their hyphenated form.
+#[cfg(feature = "uuid")]
+impl_text_value!(uuid::Uuid);
+
+// Decimals as text, so that no precision is lost on the way to the database.
+#[cfg(feature = "rust_decimal")]
+impl_text_value!(rust_decimal::Decimal);
+
+// JSON documents serialized as text.
+#[cfg(feature = "serde_json")]
+impl_text_value!(serde_json::Value);